@@ -0,0 +1,112 @@
+use image::AnimationDecoder;
+use std::io::BufReader;
+use std::path::Path;
+
+use super::cache::{cache_dir, cache_key, write_cache_file};
+use super::detect::{sniff_media, Sniffed};
+
+/// Browsers play GIF frame delays this short at 100ms; so do we
+const MIN_FRAME_DELAY: f64 = 0.02;
+const DEFAULT_FRAME_DELAY: f64 = 0.1;
+
+/// Timing of one play-through of an animated image
+pub struct AnimationInfo {
+    pub frame_count: u32,
+    pub duration: f64, // seconds
+    pub is_vfr: bool,  // frame delays differ
+}
+
+/// Decoded frames of an animated GIF, WebP or APNG. AVIF/HEIF sequences
+/// aren't covered - ffmpeg reads those as ordinary video.
+fn decode_frames(path: &str) -> Option<image::Frames<'static>> {
+    let Ok(Some(Sniffed::AnimatedImage(format))) = sniff_media(path) else {
+        return None;
+    };
+    let reader = BufReader::new(std::fs::File::open(path).ok()?);
+
+    match format {
+        "gif" => image::codecs::gif::GifDecoder::new(reader).ok().map(|d| d.into_frames()),
+        "webp" => image::codecs::webp::WebPDecoder::new(reader).ok().map(|d| d.into_frames()),
+        "apng" => image::codecs::png::PngDecoder::new(reader)
+            .and_then(|d| d.apng())
+            .ok()
+            .map(|d| d.into_frames()),
+        _ => None,
+    }
+}
+
+fn frame_delay(frame: &image::Frame) -> f64 {
+    let (numer, denom) = frame.delay().numer_denom_ms();
+    let secs = numer as f64 / denom.max(1) as f64 / 1000.0;
+    if secs < MIN_FRAME_DELAY { DEFAULT_FRAME_DELAY } else { secs }
+}
+
+/// Frame count and native duration, from the frame delays themselves -
+/// ffprobe reports nothing useful for animated WebP and rounds GIF timing
+pub fn probe_animation(path: &str) -> Option<AnimationInfo> {
+    let mut frame_count = 0;
+    let mut duration = 0.0;
+    let mut first_delay = None;
+    let mut is_vfr = false;
+
+    for frame in decode_frames(path)? {
+        let delay = frame_delay(&frame.ok()?);
+        match first_delay {
+            None => first_delay = Some(delay),
+            Some(first) if (first - delay).abs() > 0.001 => is_vfr = true,
+            _ => {}
+        }
+        frame_count += 1;
+        duration += delay;
+    }
+
+    (frame_count > 0).then_some(AnimationInfo { frame_count, duration, is_vfr })
+}
+
+/// Path ffmpeg should read for an animated image: its frames as cached
+/// PNGs plus an ffconcat list carrying each frame's delay, so export gets
+/// the exact timing on every ffmpeg build. Falls back to the original for
+/// formats we don't decode.
+pub fn export_source_for_animation(path: &str) -> Result<String, String> {
+    let frames_dir = cache_dir("animations")?.join(cache_key(path, "frames"));
+    let list_path = frames_dir.join("frames.ffconcat");
+    if list_path.exists() {
+        return Ok(list_path.to_string_lossy().to_string());
+    }
+
+    let Some(frames) = decode_frames(path) else {
+        return Ok(path.to_string());
+    };
+    std::fs::create_dir_all(&frames_dir)
+        .map_err(|e| format!("Failed to create animation cache: {}", e))?;
+
+    let mut list = String::from("ffconcat version 1.0\n");
+    let mut last_name = None;
+    for (i, frame) in frames.enumerate() {
+        let frame = frame.map_err(|e| format!("Failed to decode animation frame: {}", e))?;
+        let name = format!("frame_{:05}.png", i);
+        let delay = frame_delay(&frame);
+        frame
+            .into_buffer()
+            .save(frames_dir.join(&name))
+            .map_err(|e| format!("Failed to write animation frame: {}", e))?;
+        list.push_str(&format!("file '{}'\nduration {:.3}\n", name, delay));
+        last_name = Some(name);
+    }
+
+    // The concat demuxer ignores the last entry's duration unless the
+    // file is listed once more
+    let Some(last_name) = last_name else {
+        return Err(format!("Animation has no frames: {}", path));
+    };
+    list.push_str(&format!("file '{}'\n", last_name));
+
+    // Written last: its presence marks a complete set of frames
+    write_cache_file(&list_path, list.as_bytes())?;
+    Ok(list_path.to_string_lossy().to_string())
+}
+
+/// Whether a source path is one of our ffconcat frame lists
+pub fn is_frame_list(path: &str) -> bool {
+    Path::new(path).extension().is_some_and(|e| e == "ffconcat")
+}
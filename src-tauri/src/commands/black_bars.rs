@@ -0,0 +1,199 @@
+use std::path::Path;
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, read_cached_json, write_cached_json};
+use super::metadata::get_metadata_fast;
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+use super::transform::CropRect;
+
+/// Points through the clip where frames are checked, and frames per point
+const SAMPLE_POINTS: usize = 6;
+const FRAMES_PER_SAMPLE: u32 = 12;
+/// Luma at or below this counts as black
+const CROPDETECT_LIMIT: u32 = 24;
+/// Bars thinner than this share of the frame are left alone
+const MIN_BAR_SHARE: f64 = 0.02;
+
+fn content_rect_cache_path(path: &str) -> Result<std::path::PathBuf, String> {
+    Ok(cache_dir("black_bars")?.join(format!("{}.json", cache_key(path, "content-rect"))))
+}
+
+/// Content rectangle from an earlier `detect_black_bars` run, if any
+pub fn cached_content_rect(path: &str) -> Option<CropRect> {
+    read_cached_json::<Option<CropRect>>(&content_rect_cache_path(path).ok()?)?
+}
+
+/// Last `crop=w:h:x:y` cropdetect logged - with reset=0 that covers every
+/// frame it saw
+fn parse_cropdetect(stderr: &str) -> Option<CropRect> {
+    let line = stderr.lines().rev().find(|l| l.contains("crop="))?;
+    let values: Vec<f64> = line[line.rfind("crop=")? + 5..]
+        .trim()
+        .split(':')
+        .map(|v| v.parse::<f64>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match values[..] {
+        [width, height, x, y] => Some(CropRect { x, y, width, height }),
+        _ => None,
+    }
+}
+
+/// Picture area of one sample point
+fn detect_at(ffmpeg_path: &Path, path: &str, time: f64) -> Option<CropRect> {
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-ss", &format!("{:.3}", time),
+            "-i", path,
+            "-map", "0:v:0",
+            "-frames:v", &FRAMES_PER_SAMPLE.to_string(),
+            "-vf", &format!("cropdetect=limit={}:round=2:reset=0", CROPDETECT_LIMIT),
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .ok()?;
+    parse_cropdetect(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Union of the picture areas found at each sample point, clamped to the
+/// frame. None when every sample was black or the bars are too thin to remove.
+fn combine_samples(samples: &[CropRect], width: f64, height: f64) -> Option<CropRect> {
+    let mut union: Option<(f64, f64, f64, f64)> = None;
+    for rect in samples {
+        if rect.width < width / 10.0 || rect.height < height / 10.0 {
+            continue; // all black
+        }
+        let (x1, y1, x2, y2) = (rect.x, rect.y, rect.x + rect.width, rect.y + rect.height);
+        union = Some(match union {
+            Some((ux1, uy1, ux2, uy2)) => (ux1.min(x1), uy1.min(y1), ux2.max(x2), uy2.max(y2)),
+            None => (x1, y1, x2, y2),
+        });
+    }
+
+    union
+        .map(|(x1, y1, x2, y2)| CropRect {
+            x: x1.max(0.0),
+            y: y1.max(0.0),
+            width: x2.min(width) - x1.max(0.0),
+            height: y2.min(height) - y1.max(0.0),
+        })
+        .filter(|r| r.width < width * (1.0 - MIN_BAR_SHARE) || r.height < height * (1.0 - MIN_BAR_SHARE))
+}
+
+fn detect_black_bars_sync(path: &str, ffmpeg_path: &Path, ffprobe_path: &Path) -> Result<Option<CropRect>, String> {
+    let cache_path = content_rect_cache_path(path)?;
+    if let Some(cached) = read_cached_json::<Option<CropRect>>(&cache_path) {
+        return Ok(cached);
+    }
+
+    let metadata = get_metadata_fast(path.to_string(), ffprobe_path, ffmpeg_path)?;
+    let (width, height) = (metadata.width as f64, metadata.height as f64);
+    if width <= 0.0 || height <= 0.0 {
+        return Err("Unknown frame size".to_string());
+    }
+
+    // Spread over the clip, clear of fades at either end. Dark scenes and
+    // black frames shrink the detected area, so the union of all samples
+    // is what's really picture.
+    let times: Vec<f64> = if metadata.media_type == "image" || metadata.duration <= 0.0 {
+        vec![0.0]
+    } else {
+        (0..SAMPLE_POINTS)
+            .map(|i| metadata.duration * (0.05 + 0.9 * i as f64 / (SAMPLE_POINTS - 1) as f64))
+            .collect()
+    };
+    let samples: Vec<CropRect> = times.into_iter().filter_map(|time| detect_at(ffmpeg_path, path, time)).collect();
+    let content_rect = combine_samples(&samples, width, height);
+
+    write_cached_json(&cache_path, &content_rect);
+    Ok(content_rect)
+}
+
+/// Find burned-in letterbox or pillarbox bars. Returns the picture area in
+/// source pixels (as displayed), or None when there are no bars to remove.
+/// The result is cached and reported in later metadata as `content_rect`.
+#[command]
+pub async fn detect_black_bars(app: AppHandle, path: String) -> Result<Option<CropRect>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    tokio::task::spawn_blocking(move || detect_black_bars_sync(&path, &ffmpeg_path, &ffprobe_path))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(width: f64, height: f64, x: f64, y: f64) -> CropRect {
+        CropRect { x, y, width, height }
+    }
+
+    fn cropdetect_line(w: u32, h: u32, x: u32, y: u32) -> String {
+        format!(
+            "[Parsed_cropdetect_0 @ 0x6000012f4000] x1:{x} x2:{} y1:{y} y2:{} w:{w} h:{h} x:{x} y:{y} pts:3003 t:0.100100 limit:0.094118 crop={w}:{h}:{x}:{y}",
+            x + w - 1,
+            y + h - 1,
+        )
+    }
+
+    #[test]
+    fn parses_full_frame_when_there_is_no_crop() {
+        let stderr = format!(
+            "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'clip.mp4':\n{}\n",
+            cropdetect_line(1920, 1080, 0, 0),
+        );
+        assert_eq!(parse_cropdetect(&stderr), Some(rect(1920.0, 1080.0, 0.0, 0.0)));
+        assert_eq!(combine_samples(&[rect(1920.0, 1080.0, 0.0, 0.0)], 1920.0, 1080.0), None);
+    }
+
+    #[test]
+    fn takes_the_last_crop_logged() {
+        let stderr = [
+            cropdetect_line(1920, 800, 0, 140),
+            cropdetect_line(1920, 816, 0, 132),
+            "frame=   12 fps=0.0 q=-0.0 Lsize=N/A time=00:00:00.48 bitrate=N/A speed=4.1x".to_string(),
+        ]
+        .join("\n");
+        assert_eq!(parse_cropdetect(&stderr), Some(rect(1920.0, 816.0, 0.0, 132.0)));
+    }
+
+    #[test]
+    fn parses_odd_dimensions() {
+        let stderr = cropdetect_line(1437, 1079, 241, 1);
+        assert_eq!(parse_cropdetect(&stderr), Some(rect(1437.0, 1079.0, 241.0, 1.0)));
+    }
+
+    #[test]
+    fn ignores_output_without_a_crop() {
+        assert_eq!(parse_cropdetect(""), None);
+        assert_eq!(parse_cropdetect("[Parsed_cropdetect_0 @ 0x1] crop=1920:800"), None);
+        assert_eq!(parse_cropdetect("[Parsed_cropdetect_0 @ 0x1] crop=1920:abc:0:0"), None);
+    }
+
+    #[test]
+    fn combines_varying_crops_across_samples() {
+        // A dark scene shrinks one sample; the union is the real picture
+        let samples = [
+            rect(1920.0, 800.0, 0.0, 140.0),
+            rect(1200.0, 500.0, 360.0, 290.0),
+            rect(1920.0, 804.0, 0.0, 138.0),
+        ];
+        assert_eq!(combine_samples(&samples, 1920.0, 1080.0), Some(rect(1920.0, 804.0, 0.0, 138.0)));
+    }
+
+    #[test]
+    fn skips_black_samples_and_thin_bars() {
+        let black = rect(16.0, 16.0, 952.0, 532.0);
+        assert_eq!(combine_samples(&[black], 1920.0, 1080.0), None);
+        assert_eq!(
+            combine_samples(&[black, rect(1440.0, 1080.0, 240.0, 0.0)], 1920.0, 1080.0),
+            Some(rect(1440.0, 1080.0, 240.0, 0.0)),
+        );
+        // 10 px of 1080 is under MIN_BAR_SHARE
+        assert_eq!(combine_samples(&[rect(1920.0, 1070.0, 0.0, 5.0)], 1920.0, 1080.0), None);
+    }
+}
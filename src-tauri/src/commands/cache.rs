@@ -0,0 +1,109 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Matches `identifier` in tauri.conf.json so we share Tauri's app cache dir
+const APP_IDENTIFIER: &str = "com.quickcuts.video";
+
+/// Custom URI scheme that serves cached images to the webview
+pub const THUMB_SCHEME: &str = "qcthumb";
+
+/// Cache sub-directories that may be served over the thumbnail scheme
+pub const SERVED_KINDS: &[&str] = &["thumbs", "frames", "filmstrips"];
+
+/// Root cache directory for generated assets
+pub fn cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(APP_IDENTIFIER)
+}
+
+/// Get (and create) a cache sub-directory, e.g. "thumbs"
+pub fn cache_dir(kind: &str) -> Result<PathBuf, String> {
+    let dir = cache_root().join(kind);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    Ok(dir)
+}
+
+/// FNV-1a hash - stable across Rust versions, unlike DefaultHasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Cache key for arbitrary derived content (e.g. a serialized timeline)
+pub fn content_key(input: &str) -> String {
+    format!("{:016x}", fnv1a(input.as_bytes()))
+}
+
+/// Build a cache key for a source file and variant (e.g. "thumb-200").
+/// Size and mtime are included so edited files get fresh entries.
+pub fn cache_key(path: &str, variant: &str) -> String {
+    let (size, mtime) = std::fs::metadata(path)
+        .map(|m| {
+            let mtime = m
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis())
+                .unwrap_or(0);
+            (m.len(), mtime)
+        })
+        .unwrap_or((0, 0));
+
+    content_key(&format!("{}|{}|{}|{}", path, size, mtime, variant))
+}
+
+/// URL the webview uses to load a cached file.
+/// Windows webviews expose custom schemes as http://<scheme>.localhost
+pub fn cache_url(kind: &str, file_name: &str) -> String {
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}/{}", THUMB_SCHEME, kind, file_name)
+    } else {
+        format!("{}://localhost/{}/{}", THUMB_SCHEME, kind, file_name)
+    }
+}
+
+/// Temporary sibling path for `target`. Keeps the extension so ffmpeg can
+/// still infer the output format from it.
+pub fn temp_cache_path(target: &Path) -> PathBuf {
+    let stem = target.file_stem().and_then(|s| s.to_str()).unwrap_or("cache");
+    let ext = target.extension().and_then(|e| e.to_str()).unwrap_or("tmp");
+    target.with_file_name(format!("{}.{}.part.{}", stem, uuid::Uuid::new_v4(), ext))
+}
+
+/// Move a finished temp file into place so a concurrent request never
+/// serves a half-written image
+pub fn commit_cache_file(temp: &Path, target: &Path) -> Result<(), String> {
+    std::fs::rename(temp, target).map_err(|e| {
+        let _ = std::fs::remove_file(temp);
+        format!("Failed to write cache file: {}", e)
+    })
+}
+
+/// Write bytes into the cache atomically
+pub fn write_cache_file(target: &Path, data: &[u8]) -> Result<(), String> {
+    let temp = temp_cache_path(target);
+    std::fs::write(&temp, data).map_err(|e| format!("Failed to write cache file: {}", e))?;
+    commit_cache_file(&temp, target)
+}
+
+/// Result of an earlier analysis stored as JSON, if it's there and parses
+pub fn read_cached_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let data = std::fs::read(path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+/// Store an analysis result as JSON. Best effort - a failed write only
+/// means it's computed again next time.
+pub fn write_cached_json<T: Serialize>(path: &Path, value: &T) {
+    if let Ok(json) = serde_json::to_vec(value) {
+        let _ = write_cache_file(path, &json);
+    }
+}
@@ -0,0 +1,222 @@
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::path::Path;
+
+/// Where a media file's capture time came from, most trustworthy first
+pub const SOURCE_QUICKTIME_APPLE: &str = "quicktime_apple"; // com.apple.quicktime.creationdate
+pub const SOURCE_QUICKTIME: &str = "quicktime"; // MP4/MOV creation_time
+pub const SOURCE_EXIF: &str = "exif"; // DateTimeOriginal (+ OffsetTimeOriginal)
+pub const SOURCE_FILENAME: &str = "filename"; // IMG_20250101_123456...
+pub const SOURCE_FILESYSTEM: &str = "filesystem"; // created, else modified
+pub const SOURCE_UNKNOWN: &str = "unknown";
+
+/// Earliest year we accept - container defaults like 1904/1970 are junk
+const MIN_PLAUSIBLE_YEAR: i32 = 1995;
+
+pub struct CaptureTime {
+    pub timestamp: i64, // milliseconds since the Unix epoch
+    pub source: &'static str,
+}
+
+fn plausible(dt: DateTime<Utc>) -> Option<i64> {
+    let max = Utc::now() + chrono::Duration::days(1);
+    if dt.year() >= MIN_PLAUSIBLE_YEAR && dt <= max {
+        Some(dt.timestamp_millis())
+    } else {
+        None
+    }
+}
+
+fn local_to_millis(naive: NaiveDateTime) -> Option<i64> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .and_then(|dt| plausible(dt.with_timezone(&Utc)))
+}
+
+/// Apple's tag keeps the local offset, e.g. "2025-01-01T12:00:00+0100"
+fn parse_apple_creation_date(value: &str) -> Option<i64> {
+    DateTime::parse_from_str(value.trim(), "%Y-%m-%dT%H:%M:%S%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value.trim()))
+        .ok()
+        .and_then(|dt| plausible(dt.with_timezone(&Utc)))
+}
+
+/// MP4/MOV creation_time is UTC, e.g. "2025-01-01T11:00:00.000000Z"
+fn parse_creation_time(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .and_then(|dt| plausible(dt.with_timezone(&Utc)))
+}
+
+/// Read the EXIF block of a photo (JPEG, TIFF, HEIF, PNG, WebP)
+pub fn read_exif(path: &str) -> Option<exif::Exif> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    exif::Reader::new().read_from_container(&mut reader).ok()
+}
+
+fn exif_ascii(exif: &exif::Exif, tag: exif::Tag) -> Option<&[u8]> {
+    match &exif.get_field(tag, exif::In::PRIMARY)?.value {
+        exif::Value::Ascii(values) => values.first().map(|v| v.as_slice()),
+        _ => None,
+    }
+}
+
+/// DateTimeOriginal, using OffsetTimeOriginal when the camera wrote one and
+/// the machine's local zone otherwise
+fn exif_capture_time(exif: &exif::Exif) -> Option<i64> {
+    let raw = exif_ascii(exif, exif::Tag::DateTimeOriginal)
+        .or_else(|| exif_ascii(exif, exif::Tag::DateTime))?;
+    let mut dt = exif::DateTime::from_ascii(raw).ok()?;
+    if let Some(offset) = exif_ascii(exif, exif::Tag::OffsetTimeOriginal) {
+        let _ = dt.parse_offset(offset);
+    }
+
+    let naive = NaiveDate::from_ymd_opt(dt.year as i32, dt.month as u32, dt.day as u32)?
+        .and_hms_opt(dt.hour as u32, dt.minute as u32, dt.second as u32)?;
+
+    match dt.offset {
+        Some(minutes) => FixedOffset::east_opt(minutes as i32 * 60)
+            .and_then(|tz| tz.from_local_datetime(&naive).single())
+            .and_then(|t| plausible(t.with_timezone(&Utc))),
+        None => local_to_millis(naive),
+    }
+}
+
+fn naive_from_parts(parts: &[u32]) -> Option<NaiveDateTime> {
+    let date = NaiveDate::from_ymd_opt(parts[0] as i32, parts[1], parts[2])?;
+    match parts.len() {
+        3 => date.and_hms_opt(0, 0, 0),
+        _ => date.and_hms_opt(parts[3], parts[4], parts[5]),
+    }
+}
+
+/// Dates embedded in phone/camera file names, interpreted as local time:
+/// `IMG_20250101_123456`, `PXL_20250101_123456789`, `20250101123456`,
+/// `Screenshot 2025-01-01 at 12.34.56`, `IMG-20250101-WA0001`
+fn filename_capture_time(path: &str) -> Option<i64> {
+    let stem = Path::new(path).file_stem()?.to_str()?;
+    let groups: Vec<&str> = stem
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|g| !g.is_empty())
+        .collect();
+
+    let num = |s: &str| s.parse::<u32>().ok();
+
+    for (i, group) in groups.iter().enumerate() {
+        let rest = &groups[i + 1..];
+        let parts: Option<Vec<u32>> = match group.len() {
+            // YYYYMMDDHHMMSS[...]
+            n if n >= 14 => [0..4, 4..6, 6..8, 8..10, 10..12, 12..14]
+                .iter()
+                .map(|r| num(&group[r.clone()]))
+                .collect(),
+            // YYYYMMDD followed by HHMMSS[mmm], or a bare date
+            8 => {
+                let date = [0..4, 4..6, 6..8].iter().map(|r| num(&group[r.clone()]));
+                match rest.first().filter(|t| t.len() >= 6) {
+                    Some(time) => date
+                        .chain([0..2, 2..4, 4..6].iter().map(|r| num(&time[r.clone()])))
+                        .collect(),
+                    None => date.collect(),
+                }
+            }
+            // YYYY-MM-DD[ HH.MM.SS]
+            4 if rest.len() >= 2 && rest[0].len() == 2 && rest[1].len() == 2 => {
+                let with_time = rest.len() >= 5 && rest[2..5].iter().all(|g| g.len() == 2);
+                let take = if with_time { 5 } else { 2 };
+                std::iter::once(num(group))
+                    .chain(rest[..take].iter().map(|g| num(g)))
+                    .collect()
+            }
+            _ => None,
+        };
+
+        if let Some(millis) = parts
+            .as_deref()
+            .and_then(naive_from_parts)
+            .and_then(local_to_millis)
+        {
+            return Some(millis);
+        }
+    }
+
+    None
+}
+
+/// Created time where supported, otherwise modified time
+fn filesystem_time(path: &str) -> Option<i64> {
+    let metadata = std::fs::metadata(path).ok()?;
+    metadata
+        .created()
+        .or_else(|_| metadata.modified())
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as i64)
+}
+
+/// Resolve when a media file was captured, checking in order: Apple
+/// QuickTime creation date, MP4/MOV creation_time, EXIF DateTimeOriginal,
+/// file name patterns, then the filesystem
+pub fn resolve_capture_time(
+    path: &str,
+    apple_creation_date: Option<&str>,
+    creation_time: Option<&str>,
+) -> CaptureTime {
+    let found = |timestamp: i64, source: &'static str| CaptureTime { timestamp, source };
+
+    if let Some(ts) = apple_creation_date.and_then(parse_apple_creation_date) {
+        return found(ts, SOURCE_QUICKTIME_APPLE);
+    }
+    if let Some(ts) = creation_time.and_then(parse_creation_time) {
+        return found(ts, SOURCE_QUICKTIME);
+    }
+    if let Some(ts) = read_exif(path).as_ref().and_then(exif_capture_time) {
+        return found(ts, SOURCE_EXIF);
+    }
+    if let Some(ts) = filename_capture_time(path) {
+        return found(ts, SOURCE_FILENAME);
+    }
+    match filesystem_time(path) {
+        Some(ts) => found(ts, SOURCE_FILESYSTEM),
+        None => found(0, SOURCE_UNKNOWN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The same wall-clock time in the machine's zone, as file names are read
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> Option<i64> {
+        local_to_millis(NaiveDate::from_ymd_opt(y, mo, d)?.and_hms_opt(h, mi, s)?)
+    }
+
+    #[test]
+    fn reads_camera_and_phone_file_names() {
+        let expected = local(2025, 1, 1, 12, 34, 56);
+        assert!(expected.is_some());
+        assert_eq!(filename_capture_time("/photos/IMG_20250101_123456.jpg"), expected);
+        assert_eq!(filename_capture_time("/photos/VID_20250101_123456.mp4"), expected);
+        assert_eq!(filename_capture_time("/photos/PXL_20250101_123456789.mp4"), expected);
+        assert_eq!(filename_capture_time("/photos/PXL_20250101_123456789.MP.jpg"), expected);
+        assert_eq!(filename_capture_time("/photos/20250101123456.jpg"), expected);
+        assert_eq!(filename_capture_time("/photos/Screenshot 2025-01-01 at 12.34.56.png"), expected);
+    }
+
+    #[test]
+    fn reads_a_bare_date_as_midnight() {
+        assert_eq!(filename_capture_time("/photos/IMG-20250101-WA0001.jpg"), local(2025, 1, 1, 0, 0, 0));
+    }
+
+    #[test]
+    fn ignores_names_without_a_date() {
+        assert_eq!(filename_capture_time("/photos/holiday.jpg"), None);
+        assert_eq!(filename_capture_time("/photos/IMG_1234.HEIC"), None);
+        assert_eq!(filename_capture_time("/photos/DSC01234.ARW"), None);
+        // Date-shaped but impossible, or before any plausible capture
+        assert_eq!(filename_capture_time("/photos/IMG_20251399_123456.jpg"), None);
+        assert_eq!(filename_capture_time("/photos/IMG_19040101_000000.jpg"), None);
+    }
+}
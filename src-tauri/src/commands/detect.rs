@@ -0,0 +1,142 @@
+use std::io::Read;
+use std::path::Path;
+
+use super::images::RAW_EXTENSIONS;
+
+/// Bytes read from the start of a file for magic-number sniffing
+const SNIFF_LEN: usize = 512;
+
+/// What the first bytes of a file say it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sniffed {
+    /// A still image; the str is the format, e.g. "jpeg", "heif", "raw"
+    Image(&'static str),
+    /// A multi-frame image (animated GIF/WebP/PNG, AVIF sequence)
+    AnimatedImage(&'static str),
+    /// A video container, e.g. "mp4", "matroska", "mpegts"
+    Video(&'static str),
+}
+
+fn read_head(path: &str) -> Result<Vec<u8>, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("File could not be read: {}", e))?;
+    let mut head = vec![0u8; SNIFF_LEN];
+    let mut filled = 0;
+    while filled < SNIFF_LEN {
+        match file.read(&mut head[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) => return Err(format!("File could not be read: {}", e)),
+        }
+    }
+    head.truncate(filled);
+    Ok(head)
+}
+
+fn has_raw_extension(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| RAW_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Animated GIFs have more than one frame - decode just enough to tell
+fn is_animated_gif(path: &str) -> bool {
+    use image::AnimationDecoder;
+
+    std::fs::File::open(path)
+        .ok()
+        .and_then(|f| image::codecs::gif::GifDecoder::new(std::io::BufReader::new(f)).ok())
+        .map(|decoder| decoder.into_frames().take(2).count() > 1)
+        .unwrap_or(false)
+}
+
+/// WebP's VP8X header carries an animation flag
+fn is_animated_webp(head: &[u8]) -> bool {
+    head.len() > 20 && &head[12..16] == b"VP8X" && head[20] & 0x02 != 0
+}
+
+/// APNG announces itself with an acTL chunk before the first IDAT
+fn is_animated_png(head: &[u8]) -> bool {
+    let idat = head.windows(4).position(|w| w == b"IDAT").unwrap_or(head.len());
+    head[..idat].windows(4).any(|w| w == b"acTL")
+}
+
+/// "BM" alone is too common at the start of text files; a bitmap follows
+/// it with one of the known DIB header sizes at byte 14
+fn is_bmp_header(head: &[u8]) -> bool {
+    const DIB_HEADER_SIZES: &[u32] = &[12, 40, 52, 56, 64, 108, 124];
+    head.get(14..18)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .is_some_and(|size| DIB_HEADER_SIZES.contains(&size))
+}
+
+/// ISO base media (MP4/MOV/HEIF/AVIF/CR3) - decided by the ftyp brand
+fn sniff_iso_bmff(head: &[u8]) -> Option<Sniffed> {
+    let brand = head.get(8..12)?;
+    Some(match brand {
+        b"heic" | b"heix" | b"heim" | b"heis" | b"mif1" | b"mif2" => Sniffed::Image("heif"),
+        b"hevc" | b"hevx" | b"msf1" => Sniffed::AnimatedImage("heif"),
+        b"avif" => Sniffed::Image("avif"),
+        b"avis" => Sniffed::AnimatedImage("avif"),
+        b"crx " => Sniffed::Image("raw"),
+        b"qt  " => Sniffed::Video("mov"),
+        b"3gp4" | b"3gp5" | b"3gp6" | b"3g2a" => Sniffed::Video("3gp"),
+        _ => Sniffed::Video("mp4"),
+    })
+}
+
+/// Identify a file from its magic bytes. `Ok(None)` means "no idea" - the
+/// caller should let ffprobe decide.
+pub fn sniff_media(path: &str) -> Result<Option<Sniffed>, String> {
+    let head = read_head(path)?;
+    if head.is_empty() {
+        return Err("Unsupported media type: file is empty".to_string());
+    }
+
+    let starts = |magic: &[u8]| head.starts_with(magic);
+    let at = |offset: usize, magic: &[u8]| head.get(offset..offset + magic.len()) == Some(magic);
+
+    let sniffed = if starts(&[0xFF, 0xD8, 0xFF]) {
+        Sniffed::Image("jpeg")
+    } else if starts(b"\x89PNG\r\n\x1a\n") {
+        if is_animated_png(&head) { Sniffed::AnimatedImage("apng") } else { Sniffed::Image("png") }
+    } else if starts(b"GIF87a") || starts(b"GIF89a") {
+        if is_animated_gif(path) { Sniffed::AnimatedImage("gif") } else { Sniffed::Image("gif") }
+    } else if starts(b"RIFF") && at(8, b"WEBP") {
+        if is_animated_webp(&head) { Sniffed::AnimatedImage("webp") } else { Sniffed::Image("webp") }
+    } else if starts(b"RIFF") && at(8, b"AVI ") {
+        Sniffed::Video("avi")
+    } else if starts(b"FUJIFILMCCD-RAW") || starts(b"IIRO") || starts(b"IIRS") || starts(b"IIU\0") {
+        Sniffed::Image("raw")
+    } else if starts(b"II*\0") || starts(b"MM\0*") {
+        // DNG/CR2/NEF/ARW are TIFF containers too
+        if has_raw_extension(path) { Sniffed::Image("raw") } else { Sniffed::Image("tiff") }
+    } else if starts(b"BM") && is_bmp_header(&head) {
+        Sniffed::Image("bmp")
+    } else if at(4, b"ftyp") {
+        match sniff_iso_bmff(&head) {
+            Some(s) => s,
+            None => return Ok(None),
+        }
+    } else if at(4, b"moov") || at(4, b"mdat") || at(4, b"wide") || at(4, b"free") || at(4, b"skip") {
+        // Old QuickTime files without an ftyp box
+        Sniffed::Video("mov")
+    } else if starts(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        Sniffed::Video("matroska")
+    } else if starts(&[0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11]) {
+        Sniffed::Video("asf")
+    } else if starts(b"FLV") {
+        Sniffed::Video("flv")
+    } else if starts(&[0x00, 0x00, 0x01, 0xBA]) || starts(&[0x00, 0x00, 0x01, 0xB3]) {
+        Sniffed::Video("mpeg")
+    } else if head[0] == 0x47 && head.get(188) == Some(&0x47) {
+        Sniffed::Video("mpegts")
+    } else if head.get(4) == Some(&0x47) && head.get(196) == Some(&0x47) {
+        // M2TS/MTS: 188-byte packets behind a 4-byte timestamp
+        Sniffed::Video("m2ts")
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(sniffed))
+}
@@ -5,7 +5,14 @@ use std::path::Path;
 use std::process::Stdio;
 use tauri::{command, AppHandle, Emitter};
 
+use super::animation::{export_source_for_animation, is_frame_list};
+use super::images::export_source_for_still;
+use super::live_photo::{LivePhotoSource, MODE_MOTION, MODE_STILL_WITH_INTRO};
+use super::metadata::{has_irregular_frame_timing, is_interlaced, probe_scan_format};
+use super::sequence::SequenceInput;
 use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+use super::transform::{crop_filter, fit_filters, ClipTransform, CropRect};
+use super::waveform::probe_audio;
 
 fn debug_log(msg: &str) {
     if let Ok(mut file) = OpenOptions::new()
@@ -25,9 +32,34 @@ pub struct ExportConfig {
     pub codec: String,
     pub framerate: Option<f64>,
     pub bitrate: Option<u64>,
+    #[serde(default)]
+    pub deinterlace_double_rate: bool, // one output frame per field (50i -> 50p)
+    #[serde(default)]
+    pub frame_rate_conversion: Option<String>, // "drop" (default), "blend" or "interpolate"
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How sources at another frame rate reach the export rate: dropping and
+/// duplicating frames, blending neighbouring frames, or motion-compensated
+/// interpolation (smoothest on pans, and many times slower)
+pub const FPS_CONVERSION_DROP: &str = "drop";
+pub const FPS_CONVERSION_BLEND: &str = "blend";
+pub const FPS_CONVERSION_INTERPOLATE: &str = "interpolate";
+
+impl ExportConfig {
+    pub fn output_framerate(&self) -> f64 {
+        self.framerate.unwrap_or(30.0)
+    }
+
+    fn fps_conversion(&self) -> &str {
+        match self.frame_rate_conversion.as_deref() {
+            Some(FPS_CONVERSION_BLEND) => FPS_CONVERSION_BLEND,
+            Some(FPS_CONVERSION_INTERPOLATE) => FPS_CONVERSION_INTERPOLATE,
+            _ => FPS_CONVERSION_DROP,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MediaItem {
     pub path: String,
     pub media_type: String,
@@ -35,6 +67,152 @@ pub struct MediaItem {
     pub width: Option<u32>,
     pub height: Option<u32>,
     pub framerate: Option<f64>,
+    #[serde(default)]
+    pub has_audio: Option<bool>, // None = unknown, probed before export
+    #[serde(default)]
+    pub is_animated: bool, // animated GIF/WebP/PNG - loops to fill `duration`
+    #[serde(default)]
+    pub loop_duration: Option<f64>, // one play-through of an animated image
+    #[serde(default)]
+    pub loops: Option<u32>, // play this many times (animations: instead of `duration`)
+    #[serde(default)]
+    pub image_sequence: Option<SequenceInput>, // `path` is an image2 pattern
+    #[serde(default)]
+    pub live_photo: Option<LivePhotoSource>, // `path` is the still
+    #[serde(default)]
+    pub field_order: Option<String>, // None = unknown, probed before export
+    #[serde(default)]
+    pub pixel_aspect_ratio: Option<f64>,
+    #[serde(default)]
+    pub is_vfr: Option<bool>, // None = unknown, probed before export
+    #[serde(default)]
+    pub speed: Option<f64>, // 0.25-8x, None = 1x
+    #[serde(default)]
+    pub mute_audio: bool, // silence instead of pitch-preserved, time-stretched audio
+    #[serde(default)]
+    pub playback: Option<String>, // "forward" (default), "reverse" or "boomerang"
+    #[serde(default)]
+    pub transform: Option<ClipTransform>, // crop, rotation, flip, zoom; None = centre-fit
+    #[serde(default)]
+    pub content_rect: Option<CropRect>, // picture inside burned-in black bars
+    #[serde(default)]
+    pub keep_black_bars: bool, // don't crop to `content_rect`
+    #[serde(default)]
+    pub trim_start: Option<f64>, // in point in the source; set = play only `duration` from here
+}
+
+pub const MIN_SPEED: f64 = 0.25;
+pub const MAX_SPEED: f64 = 8.0;
+
+impl MediaItem {
+    /// Clips only the filter graph can read: looped animations, image
+    /// sequences and anything not played once forward at 1x have no
+    /// stream to copy or plain input to re-encode
+    fn needs_filter_graph(&self) -> bool {
+        self.is_animated || self.image_sequence.is_some() || self.has_playback_modes()
+    }
+
+    /// Speed, direction and loops apply to video clips; stills just hold
+    /// and animations already loop to fill their duration
+    fn supports_playback_modes(&self) -> bool {
+        self.media_type == "video" && !self.is_animated
+    }
+
+    pub fn speed(&self) -> f64 {
+        if !self.supports_playback_modes() {
+            return 1.0;
+        }
+        self.speed.unwrap_or(1.0).clamp(MIN_SPEED, MAX_SPEED)
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.supports_playback_modes() && self.playback.as_deref() == Some("reverse")
+    }
+
+    pub fn is_boomerang(&self) -> bool {
+        self.supports_playback_modes() && self.playback.as_deref() == Some("boomerang")
+    }
+
+    pub fn loop_count(&self) -> u32 {
+        if !self.supports_playback_modes() {
+            return 1;
+        }
+        self.loops.unwrap_or(1).max(1)
+    }
+
+    pub fn has_playback_modes(&self) -> bool {
+        (self.speed() - 1.0).abs() > f64::EPSILON
+            || self.is_reversed()
+            || self.is_boomerang()
+            || self.loop_count() > 1
+            || (self.supports_playback_modes() && self.mute_audio)
+    }
+
+    /// Whether the clip is framed other than by plain centre-fit
+    pub fn changes_framing(&self) -> bool {
+        self.black_bar_crop().is_some() || self.transform.as_ref().is_some_and(|t| !t.is_identity())
+    }
+
+    /// Picture area to keep when the source has burned-in bars. A crop of
+    /// the clip's own is drawn on the full frame, so it replaces this one.
+    fn black_bar_crop(&self) -> Option<&CropRect> {
+        if self.keep_black_bars || self.transform.as_ref().is_some_and(|t| t.has_crop()) {
+            return None;
+        }
+        self.content_rect.as_ref()
+    }
+
+    /// In point of a trimmed video clip, e.g. one scene of a split recording
+    pub fn trim(&self) -> Option<f64> {
+        if !self.supports_playback_modes() || self.image_sequence.is_some() {
+            return None;
+        }
+        self.trim_start.map(|t| t.max(0.0))
+    }
+
+    /// Loops `-stream_loop` can't make: a boomerang would lose its reversed
+    /// half, a trimmed input would restart at the top of the file rather
+    /// than the in point, and a sequence would run on into numbered files
+    /// past its last frame. These play as repeated segments instead, each
+    /// reading the input again, so no pass is held in memory to repeat.
+    fn repeats_as_segments(&self) -> bool {
+        self.loop_count() > 1
+            && (self.is_boomerang() || self.trim().is_some() || self.image_sequence.is_some())
+    }
+
+    /// Length of one pass when the input itself is read in a loop
+    fn input_loop_duration(&self) -> Option<f64> {
+        if self.is_animated {
+            return self.loop_duration;
+        }
+        (self.loop_count() > 1 && !self.repeats_as_segments()).then_some(self.duration)
+    }
+
+    /// Seconds the item fills in the export: `duration` after speed,
+    /// boomerang and loops
+    pub fn timeline_duration(&self) -> f64 {
+        let passes = if self.is_boomerang() { 2.0 } else { 1.0 };
+        self.duration / self.speed() * passes * self.loop_count() as f64
+    }
+}
+
+/// atempo steps for `speed`. Each stays within 0.5-2.0, the range every
+/// ffmpeg build accepts; atempo keeps the pitch.
+fn atempo_chain(speed: f64) -> Vec<String> {
+    let mut remaining = speed;
+    let mut steps = Vec::new();
+    while remaining < 0.5 {
+        steps.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    while remaining > 2.0 {
+        steps.push("atempo=2.0".to_string());
+        remaining /= 2.0;
+    }
+    if (remaining - 1.0).abs() > f64::EPSILON {
+        steps.push(format!("atempo={}", remaining));
+    }
+    steps
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,12 +229,75 @@ pub struct ExportProgress {
     pub progress: f64,
     pub current_file: Option<String>,
     pub error: Option<String>,
+    pub warning: Option<String>, // export finished, but something looks off
 }
 
 fn emit_progress(app: &AppHandle, progress: ExportProgress) {
     let _ = app.emit("export-progress", progress);
 }
 
+/// Audio and video ending further apart than this means drift
+const AV_DRIFT_THRESHOLD_SECS: f64 = 0.2;
+
+#[derive(Debug, Deserialize)]
+struct OutputStream {
+    codec_type: Option<String>,
+    duration: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputProbe {
+    streams: Option<Vec<OutputStream>>,
+}
+
+/// Compare the exported audio and video lengths. Returns a warning when
+/// they end more than `AV_DRIFT_THRESHOLD_SECS` apart.
+fn check_av_alignment(app: &AppHandle, output_path: &str) -> Option<String> {
+    let ffprobe_path = get_ffprobe_path(app).ok()?;
+    let output = std::process::Command::new(&ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-show_entries", "stream=codec_type,duration",
+            output_path,
+        ])
+        .output()
+        .ok()?;
+
+    let streams = serde_json::from_slice::<OutputProbe>(&output.stdout).ok()?.streams?;
+    let duration_of = |kind: &str| {
+        streams
+            .iter()
+            .find(|s| s.codec_type.as_deref() == Some(kind))
+            .and_then(|s| s.duration.as_deref())
+            .and_then(|d| d.parse::<f64>().ok())
+    };
+    let (video, audio) = (duration_of("video")?, duration_of("audio")?);
+
+    let drift = (video - audio).abs();
+    if drift <= AV_DRIFT_THRESHOLD_SECS {
+        return None;
+    }
+    log::warn!("A/V drift in {}: video {:.3}s, audio {:.3}s", output_path, video, audio);
+    Some(format!(
+        "Audio and video end {:.2}s apart (video {:.2}s, audio {:.2}s) - the export may be out of sync",
+        drift, video, audio
+    ))
+}
+
+/// Report the export as finished, with a warning if its audio and video
+/// don't line up
+fn emit_complete(app: &AppHandle, output_path: &str) {
+    let warning = check_av_alignment(app, output_path);
+    emit_progress(app, ExportProgress {
+        stage: "complete".to_string(),
+        progress: 100.0,
+        current_file: None,
+        error: None,
+        warning,
+    });
+}
+
 /// Check if VideoToolbox hardware encoder is available
 fn is_videotoolbox_available(app: &AppHandle) -> bool {
     let ffmpeg_path = match get_ffmpeg_path(app) {
@@ -81,7 +322,7 @@ fn is_videotoolbox_available(app: &AppHandle) -> bool {
 
 /// Check if a video segment can be stream-copied
 fn can_stream_copy(item: &MediaItem, target_width: u32, target_height: u32, target_fps: f64) -> bool {
-    if item.media_type != "video" {
+    if item.media_type != "video" || item.needs_filter_graph() || item.changes_framing() || item.trim().is_some() {
         return false;
     }
 
@@ -105,8 +346,11 @@ fn can_fast_concat(media_items: &[MediaItem], cover: &CoverConfig) -> bool {
         return false;
     }
 
-    // All items must be videos (no images)
-    if media_items.iter().any(|m| m.media_type != "video") {
+    // All items must be plain videos (no images, animations or sequences)
+    if media_items
+        .iter()
+        .any(|m| m.media_type != "video" || m.needs_filter_graph() || m.changes_framing() || m.trim().is_some())
+    {
         return false;
     }
 
@@ -116,9 +360,14 @@ fn can_fast_concat(media_items: &[MediaItem], cover: &CoverConfig) -> bool {
     let ref_height = first.height;
     let ref_fps = first.framerate;
 
-    // All videos must have same dimensions and framerate
+    // VFR clips drift out of sync once their timestamps are concatenated
+    if media_items.iter().any(|m| m.is_vfr == Some(true)) {
+        return false;
+    }
+
+    // All videos must have same dimensions, framerate and audio layout
     media_items.iter().all(|m| {
-        m.width == ref_width && m.height == ref_height &&
+        m.width == ref_width && m.height == ref_height && m.has_audio == first.has_audio &&
         m.framerate.map_or(false, |f| ref_fps.map_or(false, |rf| (f - rf).abs() < 0.5))
     })
 }
@@ -139,6 +388,7 @@ fn export_fast_concat(
         progress: 10.0,
         current_file: Some("Fast concat (no re-encoding)...".to_string()),
         error: None,
+        warning: None,
     });
 
     // Create temp file list for concat demuxer
@@ -188,12 +438,7 @@ fn export_fast_concat(
     // Clean up temp file
     let _ = std::fs::remove_file(&list_path);
 
-    emit_progress(app, ExportProgress {
-        stage: "complete".to_string(),
-        progress: 100.0,
-        current_file: None,
-        error: None,
-    });
+    emit_complete(app, &final_output);
 
     Ok(final_output)
 }
@@ -209,6 +454,7 @@ fn run_ffmpeg(
         progress: 50.0,
         current_file: Some(format!("{}...", stage_msg)),
         error: None,
+        warning: None,
     });
 
     let ffmpeg_path = get_ffmpeg_path(app)?;
@@ -245,13 +491,15 @@ fn run_ffmpeg(
 }
 
 /// Build complex filter graph for single-pass encoding
-fn build_filter_graph(
+pub fn build_filter_graph(
     media_items: &[MediaItem],
     cover: &CoverConfig,
+    config: &ExportConfig,
     width: u32,
     height: u32,
-    framerate: f64,
 ) -> (Vec<String>, String) {
+    let framerate = config.output_framerate();
+    let media_items = &expand_repeats(media_items);
     let mut inputs: Vec<String> = Vec::new();
     let mut filter_parts: Vec<String> = Vec::new();
     let mut concat_inputs: Vec<String> = Vec::new();
@@ -314,37 +562,112 @@ fn build_filter_graph(
             inputs.push(format!("anullsrc=r=48000:cl=stereo:d={}", item.duration));
 
             filter_parts.push(format!(
-                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black,setsar=1,fps={},format=yuv420p[v{}]",
-                stream_idx, width, height, width, height, framerate, i
+                "[{}:v]{}{},setsar=1,fps={},format=yuv420p[v{}]",
+                stream_idx,
+                source_filters(item, false),
+                fit_filters(item.transform.as_ref(), width, height),
+                framerate,
+                i
             ));
             filter_parts.push(format!("[{}:a]aformat=sample_rates=48000:channel_layouts=stereo[a{}]", stream_idx + 1, i));
 
             concat_inputs.push(format!("[v{}][a{}]", i, i));
             stream_idx += 2;
         } else {
+            // Plain repeats re-read the input instead of buffering frames
+            if item.loop_count() > 1 && !item.repeats_as_segments() {
+                inputs.extend(["-stream_loop".to_string(), (item.loop_count() - 1).to_string()]);
+            }
+            if let Some(start) = item.trim() {
+                inputs.extend([
+                    "-ss".to_string(), format!("{:.3}", start),
+                    "-t".to_string(), format!("{:.3}", item.duration),
+                ]);
+            }
+            if item.is_animated {
+                // Loop the animation for as long as the item lasts
+                inputs.extend([
+                    "-stream_loop".to_string(), "-1".to_string(),
+                    "-t".to_string(), item.duration.to_string(),
+                ]);
+                if is_frame_list(&item.path) {
+                    inputs.extend(["-f".to_string(), "concat".to_string(), "-safe".to_string(), "0".to_string()]);
+                }
+            }
+            if let Some(seq) = &item.image_sequence {
+                // One input for the whole timelapse, stopping at its last
+                // frame even when more numbered files follow on disk
+                inputs.extend([
+                    "-f".to_string(), "image2".to_string(),
+                    "-framerate".to_string(), seq.framerate.to_string(),
+                    "-start_number".to_string(), seq.start_number.to_string(),
+                    "-t".to_string(), format!("{:.3}", seq.frame_count as f64 / seq.framerate),
+                ]);
+            }
             inputs.extend(["-i".to_string(), item.path.clone()]);
 
-            let needs_processing = item.width.map_or(true, |w| w != width)
+            // Animations are variable-rate, sequence frames are stills and
+            // speed changes retime every frame, whatever their size. VFR
+            // sources are normalised to constant frame rate by the fps filter.
+            let mut source = source_filters(item, config.deinterlace_double_rate);
+            if (item.speed() - 1.0).abs() > f64::EPSILON {
+                source.push_str(&format!("setpts=(PTS-STARTPTS)/{},", item.speed()));
+            }
+            let needs_processing = item.needs_filter_graph()
+                || !source.is_empty()
+                || item.changes_framing()
+                || item.is_vfr == Some(true)
+                || item.width.map_or(true, |w| w != width)
                 || item.height.map_or(true, |h| h != height)
                 || item.framerate.map_or(true, |f| (f - framerate).abs() > 0.5);
 
             if needs_processing {
                 filter_parts.push(format!(
-                    "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black,setsar=1,fps={},format=yuv420p[v{}]",
-                    stream_idx, width, height, width, height, framerate, i
+                    "[{}:v]{}{},setsar=1,{},format=yuv420p[v{}]",
+                    stream_idx,
+                    source,
+                    fit_filters(item.transform.as_ref(), width, height),
+                    fps_filter(item, framerate, config.fps_conversion()),
+                    i
                 ));
             } else {
                 // Even for copy, ensure consistent format
                 filter_parts.push(format!("[{}:v]format=yuv420p,setsar=1[v{}]", stream_idx, i));
             }
+            // Reversing after fps buffers output frames, not source frames
+            let video_label = push_direction_filters(&mut filter_parts, item, &format!("v{}", i), false);
+
+            // Silent and muted clips (and animated images) get a generated
+            // track so every concat segment has audio
+            let silent = item.has_audio == Some(false) || (item.supports_playback_modes() && item.mute_audio);
+            let audio_idx = if silent {
+                inputs.extend(["-f".to_string(), "lavfi".to_string(), "-i".to_string()]);
+                inputs.push(format!("anullsrc=r=48000:cl=stereo:d={}", item.timeline_duration()));
+                stream_idx + 1
+            } else {
+                stream_idx
+            };
 
-            filter_parts.push(format!(
-                "[{}:a]aformat=sample_rates=48000:channel_layouts=stereo[a{}]",
-                stream_idx, i
-            ));
-
-            concat_inputs.push(format!("[v{}][a{}]", i, i));
-            stream_idx += 1;
+            let audio_label = if silent {
+                filter_parts.push(format!(
+                    "[{}:a]aformat=sample_rates=48000:channel_layouts=stereo[a{}]",
+                    audio_idx, i
+                ));
+                format!("a{}", i)
+            } else {
+                // Stretch/squeeze VFR audio to its timestamps so it stays with the picture
+                let mut chain = Vec::new();
+                if item.is_vfr == Some(true) {
+                    chain.push("aresample=async=1:first_pts=0".to_string());
+                }
+                chain.push("aformat=sample_rates=48000:channel_layouts=stereo".to_string());
+                chain.extend(atempo_chain(item.speed()));
+                filter_parts.push(format!("[{}:a]{}[a{}]", audio_idx, chain.join(","), i));
+                push_direction_filters(&mut filter_parts, item, &format!("a{}", i), true)
+            };
+
+            concat_inputs.push(format!("[{}][{}]", video_label, audio_label));
+            stream_idx = audio_idx + 1;
         }
     }
 
@@ -360,10 +683,238 @@ fn build_filter_graph(
     (inputs, filter_complex)
 }
 
+/// Reverse and boomerang filters for the finished `[label]` stream of an
+/// item. Returns the label of the result. Reversing holds the whole pass in
+/// memory, so passes are capped at `MAX_REVERSED_PASS`.
+fn push_direction_filters(filter_parts: &mut Vec<String>, item: &MediaItem, label: &str, audio: bool) -> String {
+    let (reverse, split, concat) = if audio {
+        ("areverse", "asplit", "concat=n=2:v=0:a=1")
+    } else {
+        ("reverse", "split", "concat=n=2:v=1:a=0")
+    };
+
+    if item.is_reversed() {
+        filter_parts.push(format!("[{l}]{r}[{l}r]", l = label, r = reverse));
+        format!("{}r", label)
+    } else if item.is_boomerang() {
+        filter_parts.push(format!(
+            "[{l}]{s}[{l}f][{l}b];[{l}b]{r}[{l}br];[{l}f][{l}br]{c}[{l}bm]",
+            l = label, s = split, r = reverse, c = concat
+        ));
+        format!("{}bm", label)
+    } else {
+        label.to_string()
+    }
+}
+
+/// Play loops `-stream_loop` can't make as that many single-pass copies
+/// of the item, one concat segment each
+fn expand_repeats(media_items: &[MediaItem]) -> Vec<MediaItem> {
+    let mut expanded = Vec::with_capacity(media_items.len());
+    for item in media_items {
+        if !item.repeats_as_segments() {
+            expanded.push(item.clone());
+            continue;
+        }
+        for _ in 0..item.loop_count() {
+            expanded.push(MediaItem { loops: Some(1), ..item.clone() });
+        }
+    }
+    expanded
+}
+
+/// Filters a video needs before it is fitted to the frame: deinterlacing
+/// (bwdif follows the stream's field order; `double_rate` keeps one frame
+/// per field) and stretching anamorphic pixels square, so the fit scale
+/// works from the display aspect ratio - then cropping off black bars and
+/// the clip's own crop, rotation and flips. Empty for progressive
+/// square-pixel sources left as they are.
+fn source_filters(item: &MediaItem, double_rate: bool) -> String {
+    let mut filters = String::new();
+    if is_interlaced(item.field_order.as_deref()) {
+        let mode = if double_rate { "send_field" } else { "send_frame" };
+        filters.push_str(&format!("bwdif=mode={}:parity=auto:deint=interlaced,", mode));
+    }
+    if item.pixel_aspect_ratio.is_some_and(|par| (par - 1.0).abs() > 0.01) {
+        filters.push_str("scale=trunc(iw*sar/2)*2:ih,setsar=1,");
+    }
+    if let (Some(rect), Some(w), Some(h)) = (item.black_bar_crop(), item.width, item.height) {
+        filters.push_str(&crop_filter(rect, w, h));
+    }
+    if let Some(transform) = &item.transform {
+        // Keyframes are in source time; a trimmed input starts at its in point
+        let transform = transform.shifted(item.trim().unwrap_or(0.0));
+        filters.push_str(&transform.source_filters(item.width, item.height, item.input_loop_duration()));
+    }
+    filters
+}
+
+/// Filter taking a video item to the export frame rate. Blending and
+/// interpolation only apply to footage at a known, different rate - stills,
+/// animations and VFR sources are always retimed with `fps`.
+fn fps_filter(item: &MediaItem, framerate: f64, mode: &str) -> String {
+    let converts = item.media_type == "video"
+        && !item.is_animated
+        && item.is_vfr != Some(true)
+        && item
+            .framerate
+            .is_some_and(|f| (f * item.speed() - framerate).abs() > 0.5);
+    match mode {
+        FPS_CONVERSION_BLEND if converts => format!("format=yuv420p,framerate=fps={}", framerate),
+        FPS_CONVERSION_INTERPOLATE if converts => format!(
+            "format=yuv420p,minterpolate=fps={}:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:vsbmc=1",
+            framerate
+        ),
+        _ => format!("fps={}", framerate),
+    }
+}
+
+/// Shortest still left after a Live Photo's motion intro
+const MIN_LIVE_STILL_DURATION: f64 = 0.5;
+
+/// Replace Live Photo items with what actually plays: the still, the
+/// motion clip, or the motion clip followed by the still for the rest of
+/// the item's duration
+fn expand_live_photos(media_items: Vec<MediaItem>) -> Vec<MediaItem> {
+    let mut expanded = Vec::with_capacity(media_items.len());
+    for mut item in media_items {
+        let Some(live) = item.live_photo.take() else {
+            expanded.push(item);
+            continue;
+        };
+        let motion = MediaItem {
+            path: live.motion_path,
+            media_type: "video".to_string(),
+            duration: live.motion_duration,
+            ..Default::default()
+        };
+        match live.mode.as_str() {
+            MODE_MOTION => expanded.push(motion),
+            MODE_STILL_WITH_INTRO => {
+                item.duration = (item.duration - motion.duration).max(MIN_LIVE_STILL_DURATION);
+                expanded.push(motion);
+                expanded.push(item);
+            }
+            _ => expanded.push(item),
+        }
+    }
+    expanded
+}
+
+/// Longest pass, in output seconds, a reversed or boomerang clip may play.
+/// ffmpeg's reverse filters buffer every decoded frame of the pass - about
+/// 3 MB a frame at 1080p.
+const MAX_REVERSED_PASS: f64 = 30.0;
+
+fn check_reversed_pass(item: &MediaItem) -> Result<(), String> {
+    if !item.is_reversed() && !item.is_boomerang() {
+        return Ok(());
+    }
+    let pass = item.duration / item.speed();
+    if pass > MAX_REVERSED_PASS {
+        let name = Path::new(&item.path).file_name().map_or_else(
+            || item.path.clone(),
+            |n| n.to_string_lossy().to_string(),
+        );
+        return Err(format!(
+            "{} plays {:.0} seconds per pass; reversed and boomerang clips can play at most {:.0}. Trim it or speed it up.",
+            name, pass, MAX_REVERSED_PASS
+        ));
+    }
+    Ok(())
+}
+
+/// Stills decoded and resized at once while preparing an export. Each
+/// holds a full-resolution decode in memory, so this stays small.
+const STILL_PREP_WORKERS: usize = 4;
+
+/// Swap in ffmpeg-readable sources for stills - HEIC/HEIF, AVIF, RAW,
+/// EXIF-rotated or larger than the `width`x`height` frame - converted in
+/// parallel and cached - and animated images, as cached frame lists. Live
+/// Photos are expanded first. Also finds out which videos actually carry
+/// audio.
+pub fn prepare_export_sources(
+    app: &AppHandle,
+    media_items: &mut Vec<MediaItem>,
+    width: u32,
+    height: u32,
+) -> Result<(), String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let ffprobe_path = get_ffprobe_path(app)?;
+    *media_items = expand_live_photos(std::mem::take(media_items));
+
+    let mut stills: Vec<&mut MediaItem> = media_items
+        .iter_mut()
+        .filter(|item| item.media_type == "image")
+        .collect();
+    if !stills.is_empty() {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(STILL_PREP_WORKERS);
+        let chunk_size = stills.len().div_ceil(workers);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = stills
+                .chunks_mut(chunk_size)
+                .map(|chunk| {
+                    let ffmpeg_path = &ffmpeg_path;
+                    scope.spawn(move || {
+                        for item in chunk.iter_mut() {
+                            // Cropped, zoomed or turned stills keep the detail they need
+                            let (frame_width, frame_height) = match &item.transform {
+                                Some(t) => {
+                                    let scale = t.detail_scale(item.width, item.height);
+                                    let (w, h) = if t.swaps_dimensions() { (height, width) } else { (width, height) };
+                                    ((w as f64 * scale).round() as u32, (h as f64 * scale).round() as u32)
+                                }
+                                None => (width, height),
+                            };
+                            item.path = export_source_for_still(&item.path, ffmpeg_path, frame_width, frame_height)?;
+                        }
+                        Ok::<(), String>(())
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().map_err(|_| "Still conversion panicked".to_string())?)
+        })?;
+    }
+
+    for item in media_items.iter() {
+        check_reversed_pass(item)?;
+    }
+
+    for item in media_items.iter_mut() {
+        if item.is_animated {
+            // A chosen number of loops overrides the item's duration
+            if let (Some(loops), Some(loop_duration)) = (item.loops, item.loop_duration) {
+                item.duration = loops.max(1) as f64 * loop_duration;
+            }
+            item.path = export_source_for_animation(&item.path)?;
+            item.has_audio = Some(false);
+        } else if item.image_sequence.is_some() {
+            item.has_audio = Some(false);
+        } else if item.media_type != "image" {
+            if item.has_audio.is_none() {
+                item.has_audio = probe_audio(&ffprobe_path, &item.path).ok().map(|(has, _)| has);
+            }
+            if item.is_vfr.is_none() && !item.needs_filter_graph() {
+                item.is_vfr = Some(has_irregular_frame_timing(&ffprobe_path, &item.path));
+            }
+            if item.field_order.is_none() || item.pixel_aspect_ratio.is_none() {
+                let (field_order, par) = probe_scan_format(&ffprobe_path, &item.path);
+                item.field_order = field_order;
+                item.pixel_aspect_ratio = Some(par);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Calculate total duration from media items and cover
 fn calculate_total_duration(media_items: &[MediaItem], cover: &CoverConfig) -> f64 {
     let cover_dur = if cover.enabled && !cover.text.is_empty() { cover.duration } else { 0.0 };
-    let media_dur: f64 = media_items.iter().map(|m| m.duration).sum();
+    let media_dur: f64 = media_items.iter().map(|m| m.timeline_duration()).sum();
     cover_dur + media_dur
 }
 
@@ -381,9 +932,13 @@ pub async fn export_video(
         progress: 0.0,
         current_file: Some("Checking hardware acceleration...".to_string()),
         error: None,
+        warning: None,
     });
 
-    let framerate = config.framerate.unwrap_or(30.0);
+    let mut media_items = media_items;
+    prepare_export_sources(&app, &mut media_items, config.width, config.height)?;
+
+    let framerate = config.output_framerate();
     let total_duration = calculate_total_duration(&media_items, &cover);
 
     // Check for hardware acceleration
@@ -398,7 +953,8 @@ pub async fn export_video(
     }
 
     // Single video without cover - check if we can stream copy
-    if media_items.len() == 1 && !cover.enabled {
+    // Animations and sequences always go through the graph
+    if media_items.len() == 1 && !cover.enabled && !media_items[0].needs_filter_graph() {
         let item = &media_items[0];
         if can_stream_copy(item, config.width, config.height, framerate) {
             return export_stream_copy(&app, item, &output_path).await;
@@ -413,14 +969,15 @@ pub async fn export_video(
         progress: 10.0,
         current_file: Some("Building filter graph...".to_string()),
         error: None,
+        warning: None,
     });
 
     let (inputs, filter_complex) = build_filter_graph(
         &media_items,
         &cover,
+        &config,
         config.width,
         config.height,
-        framerate,
     );
 
     emit_progress(&app, ExportProgress {
@@ -428,6 +985,7 @@ pub async fn export_video(
         progress: 15.0,
         current_file: Some(format!("Starting {} encode...", hw_status)),
         error: None,
+        warning: None,
     });
 
     // Build ffmpeg command
@@ -502,18 +1060,14 @@ pub async fn export_video(
         progress: 95.0,
         current_file: Some("Verifying output...".to_string()),
         error: None,
+        warning: None,
     });
 
     if !Path::new(&final_output).exists() {
         return Err("Output file was not created".to_string());
     }
 
-    emit_progress(&app, ExportProgress {
-        stage: "complete".to_string(),
-        progress: 100.0,
-        current_file: None,
-        error: None,
-    });
+    emit_complete(&app, &final_output);
 
     Ok(final_output)
 }
@@ -533,12 +1087,7 @@ async fn export_stream_copy(
 
     run_ffmpeg(app, args, "Stream copy (fast)")?;
 
-    emit_progress(app, ExportProgress {
-        stage: "complete".to_string(),
-        progress: 100.0,
-        current_file: None,
-        error: None,
-    });
+    emit_complete(app, output_path);
 
     Ok(output_path.to_string())
 }
@@ -555,8 +1104,10 @@ async fn export_single_video(
     let hw_status = if use_hw { "HW accelerated" } else { "Software" };
 
     let filter = format!(
-        "scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black,setsar=1,fps={}",
-        config.width, config.height, config.width, config.height, framerate
+        "{}{},setsar=1,{}",
+        source_filters(item, config.deinterlace_double_rate),
+        fit_filters(item.transform.as_ref(), config.width, config.height),
+        fps_filter(item, framerate, config.fps_conversion())
     );
 
     let mut args = vec![
@@ -569,6 +1120,12 @@ async fn export_single_video(
         args.extend(["-hwaccel".to_string(), "videotoolbox".to_string()]);
     }
 
+    if let Some(start) = item.trim() {
+        args.extend([
+            "-ss".to_string(), format!("{:.3}", start),
+            "-t".to_string(), format!("{:.3}", item.duration),
+        ]);
+    }
     args.extend([
         "-i".to_string(), item.path.clone(),
         "-vf".to_string(), filter,
@@ -594,6 +1151,10 @@ async fn export_single_video(
         }
     }
 
+    // The fps filter makes VFR video constant-rate; keep its audio with it
+    if item.is_vfr == Some(true) {
+        args.extend(["-af".to_string(), "aresample=async=1:first_pts=0".to_string()]);
+    }
     args.extend([
         "-c:a".to_string(), "aac".to_string(),
         "-ar".to_string(), "48000".to_string(),
@@ -605,12 +1166,7 @@ async fn export_single_video(
     let stage_msg = format!("{} encoding", hw_status);
     run_ffmpeg(app, args, &stage_msg)?;
 
-    emit_progress(app, ExportProgress {
-        stage: "complete".to_string(),
-        progress: 100.0,
-        current_file: None,
-        error: None,
-    });
+    emit_complete(app, output_path);
 
     Ok(output_path.to_string())
 }
@@ -636,3 +1192,36 @@ pub async fn get_video_duration(app: AppHandle, path: String) -> Result<f64, Str
         .parse::<f64>()
         .map_err(|e| format!("Failed to parse duration: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factors(speed: f64) -> Vec<f64> {
+        atempo_chain(speed)
+            .iter()
+            .map(|step| step.trim_start_matches("atempo=").parse::<f64>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn atempo_chain_splits_extreme_speeds_into_valid_steps() {
+        for speed in [MIN_SPEED, 0.3, 0.75, 1.5, 3.0, MAX_SPEED] {
+            let steps = factors(speed);
+            assert!(steps.iter().all(|f| (0.5..=2.0).contains(f)), "{}x: {:?}", speed, steps);
+            let product: f64 = steps.iter().product();
+            assert!((product - speed).abs() < 1e-9, "{}x multiplies back to {}", speed, product);
+        }
+    }
+
+    #[test]
+    fn atempo_chain_uses_halvings_and_doublings_at_the_limits() {
+        assert_eq!(factors(0.25), vec![0.5, 0.5]);
+        assert_eq!(factors(8.0), vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn atempo_chain_is_empty_at_normal_speed() {
+        assert!(atempo_chain(1.0).is_empty());
+    }
+}
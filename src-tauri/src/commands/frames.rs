@@ -0,0 +1,531 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use super::cache::{
+    cache_dir, cache_key, cache_url, commit_cache_file, read_cached_json, temp_cache_path, write_cache_file,
+    write_cached_json,
+};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+/// Upper bound on frames per filmstrip - keeps sprites a sane size
+const MAX_FILMSTRIP_FRAMES: u32 = 120;
+const DEFAULT_FILMSTRIP_FRAMES: u32 = 10;
+const DEFAULT_TILE_WIDTH: u32 = 160;
+/// Tiles per sprite row before wrapping
+const SPRITE_COLUMNS: u32 = 10;
+
+/// Poster candidates are taken from this many seconds at the start of a clip
+const POSTER_WINDOW_SECS: f64 = 10.0;
+const POSTER_CANDIDATES: u32 = 8;
+/// Mean luma below this is a black fade/frame
+const BLACK_LUMA_THRESHOLD: f64 = 20.0;
+/// Luma standard deviation below this is a flat, low-detail frame
+const LOW_DETAIL_THRESHOLD: f64 = 12.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilmstripFrame {
+    pub time: f64, // source timestamp in seconds
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Filmstrip {
+    pub sprite: String, // qcthumb:// URL of the packed sprite image
+    pub sprite_width: u32,
+    pub sprite_height: u32,
+    pub frames: Vec<FilmstripFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeTimingFormat {
+    duration: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeTimingStream {
+    r_frame_rate: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeTiming {
+    format: Option<ProbeTimingFormat>,
+    streams: Option<Vec<ProbeTimingStream>>,
+}
+
+/// Parse an ffprobe rational like "30000/1001"
+pub fn parse_rational(value: &str) -> Option<f64> {
+    match value.split_once('/') {
+        Some((num, den)) => {
+            let num = num.parse::<f64>().ok()?;
+            let den = den.parse::<f64>().ok()?;
+            if den > 0.0 { Some(num / den) } else { None }
+        }
+        None => value.parse::<f64>().ok(),
+    }
+}
+
+/// Duration and framerate of the first video stream
+pub fn probe_video_timing(ffprobe_path: &Path, path: &str) -> (Option<f64>, Option<f64>) {
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-select_streams", "v:0",
+            "-show_entries", "format=duration:stream=r_frame_rate",
+            path,
+        ])
+        .output();
+
+    let Ok(output) = output else { return (None, None) };
+    let Ok(probe) = serde_json::from_slice::<ProbeTiming>(&output.stdout) else {
+        return (None, None);
+    };
+
+    let duration = probe
+        .format
+        .and_then(|f| f.duration)
+        .and_then(|d| d.parse::<f64>().ok())
+        .filter(|d| *d > 0.0);
+    let framerate = probe
+        .streams
+        .and_then(|s| s.into_iter().next())
+        .and_then(|s| s.r_frame_rate)
+        .and_then(|r| parse_rational(&r))
+        .filter(|f| *f > 0.0);
+
+    (duration, framerate)
+}
+
+/// Work out how many frames to extract from the requested count/interval,
+/// never asking for more frames than the clip actually has
+fn filmstrip_frame_count(duration: f64, framerate: Option<f64>, count: Option<u32>, interval: Option<f64>) -> u32 {
+    let requested = match (count, interval) {
+        (Some(n), _) => n,
+        (None, Some(step)) if step > 0.0 => (duration / step).ceil() as u32,
+        _ => DEFAULT_FILMSTRIP_FRAMES,
+    };
+
+    let available = framerate
+        .map(|fps| (duration * fps).floor() as u32)
+        .unwrap_or(u32::MAX);
+
+    requested.min(available).clamp(1, MAX_FILMSTRIP_FRAMES)
+}
+
+/// Run ffmpeg once and write every extracted frame into `out_dir`
+fn extract_frames(
+    ffmpeg_path: &Path,
+    path: &str,
+    out_dir: &Path,
+    seek: Option<f64>,
+    filter: &str,
+    max_frames: u32,
+) -> Vec<PathBuf> {
+    let pattern = out_dir.join("frame_%04d.jpg");
+    let mut args: Vec<String> = vec!["-hide_banner".to_string(), "-v".to_string(), "error".to_string()];
+    if let Some(ss) = seek {
+        args.extend(["-ss".to_string(), format!("{:.3}", ss)]);
+    }
+    args.extend([
+        "-i".to_string(), path.to_string(),
+        "-vf".to_string(), filter.to_string(),
+        "-frames:v".to_string(), max_frames.to_string(),
+        "-q:v".to_string(), "4".to_string(),
+        "-y".to_string(), pattern.to_string_lossy().to_string(),
+    ]);
+
+    let _ = Command::new(ffmpeg_path).args(&args).output();
+
+    let mut frames: Vec<PathBuf> = std::fs::read_dir(out_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "jpg"))
+                .collect()
+        })
+        .unwrap_or_default();
+    frames.sort();
+    frames
+}
+
+/// Extract the exact frame at `time` into `output`.
+/// `-ss` before `-i` seeks to the nearest keyframe and then decodes up to the
+/// exact timestamp, and ffmpeg's default autorotate applies the display
+/// matrix. Times past the end fall back to the last frame.
+fn extract_frame_at(ffmpeg_path: &Path, path: &str, time: f64, filter: Option<&str>, output: &Path) -> bool {
+    let run = |seek_args: [&str; 2], output_args: &[&str]| {
+        let mut args: Vec<String> = vec!["-hide_banner".to_string(), "-v".to_string(), "error".to_string()];
+        args.extend(seek_args.iter().map(|a| a.to_string()));
+        args.extend(["-i".to_string(), path.to_string()]);
+        if let Some(vf) = filter {
+            args.extend(["-vf".to_string(), vf.to_string()]);
+        }
+        args.extend(output_args.iter().map(|a| a.to_string()));
+        // JPEG quality; PNG is lossless and has no use for it
+        if !output.extension().is_some_and(|e| e.eq_ignore_ascii_case("png")) {
+            args.extend(["-q:v".to_string(), "2".to_string()]);
+        }
+        args.extend(["-y".to_string(), output.to_string_lossy().to_string()]);
+        let _ = Command::new(ffmpeg_path).args(&args).output();
+        output.exists()
+    };
+
+    let seek = format!("{:.3}", time.max(0.0));
+    if run(["-ss", &seek], &["-frames:v", "1"]) {
+        return true;
+    }
+
+    // Keep overwriting the output with the final second's frames so the
+    // last decodable frame is what remains
+    run(["-sseof", "-1"], &["-update", "1"])
+}
+
+/// Sample `n` frames evenly across `[start, start + span)` in one ffmpeg
+/// pass, taking the centre of each segment. Falls back to the first
+/// decodable frame for very short or unprobeable clips.
+fn sample_evenly(
+    ffmpeg_path: &Path,
+    path: &str,
+    out_dir: &Path,
+    start: f64,
+    span: f64,
+    n: u32,
+    scale: &str,
+) -> (Vec<PathBuf>, Vec<f64>) {
+    let step = if span > 0.0 { span / n as f64 } else { 0.0 };
+    sample_every(ffmpeg_path, path, out_dir, start, step, n, scale)
+}
+
+/// Sample up to `n` frames `step` seconds apart from `start`, taking the
+/// centre of each step
+fn sample_every(
+    ffmpeg_path: &Path,
+    path: &str,
+    out_dir: &Path,
+    start: f64,
+    step: f64,
+    n: u32,
+    scale: &str,
+) -> (Vec<PathBuf>, Vec<f64>) {
+    if step > 0.0 {
+        // Seek half a step in, then let the fps filter pick one frame per step
+        let filter = format!("fps={:.6},{}", 1.0 / step, scale);
+        let frames = extract_frames(ffmpeg_path, path, out_dir, Some(start + step / 2.0), &filter, n);
+        if !frames.is_empty() {
+            let times = (0..frames.len())
+                .map(|i| start + (i as f64 + 0.5) * step)
+                .collect();
+            return (frames, times);
+        }
+    }
+
+    let frames = extract_frames(ffmpeg_path, path, out_dir, None, scale, 1);
+    let times = frames.iter().map(|_| 0.0).collect();
+    (frames, times)
+}
+
+struct FrameScore {
+    brightness: f64, // mean luma
+    contrast: f64,   // luma standard deviation
+    sharpness: f64,  // variance of the Laplacian
+}
+
+impl FrameScore {
+    fn is_usable(&self) -> bool {
+        self.brightness >= BLACK_LUMA_THRESHOLD && self.contrast >= LOW_DETAIL_THRESHOLD
+    }
+}
+
+fn score_frame(img: &image::GrayImage) -> FrameScore {
+    let (w, h) = img.dimensions();
+    let pixels = img.as_raw();
+    let count = pixels.len().max(1) as f64;
+
+    let mean = pixels.iter().map(|p| *p as f64).sum::<f64>() / count;
+    let variance = pixels
+        .iter()
+        .map(|p| (*p as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+
+    // 4-neighbour Laplacian over interior pixels
+    let mut lap_sum = 0.0;
+    let mut lap_sq_sum = 0.0;
+    let mut lap_count = 0.0;
+    for y in 1..h.saturating_sub(1) {
+        for x in 1..w.saturating_sub(1) {
+            let at = |dx: i32, dy: i32| img.get_pixel((x as i32 + dx) as u32, (y as i32 + dy) as u32)[0] as f64;
+            let lap = 4.0 * at(0, 0) - at(-1, 0) - at(1, 0) - at(0, -1) - at(0, 1);
+            lap_sum += lap;
+            lap_sq_sum += lap * lap;
+            lap_count += 1.0;
+        }
+    }
+    let sharpness = if lap_count > 0.0 {
+        let lap_mean = lap_sum / lap_count;
+        lap_sq_sum / lap_count - lap_mean * lap_mean
+    } else {
+        0.0
+    };
+
+    FrameScore {
+        brightness: mean,
+        contrast: variance.sqrt(),
+        sharpness,
+    }
+}
+
+/// Pick a representative frame for a video thumbnail.
+/// An explicit `poster_time` wins; otherwise a handful of candidates from the
+/// start of the clip are scored, skipping near-black and flat frames and
+/// preferring the sharpest one.
+pub fn select_poster_frame(
+    ffmpeg_path: &Path,
+    ffprobe_path: &Path,
+    path: &str,
+    poster_time: Option<f64>,
+    width: u32,
+) -> Option<image::DynamicImage> {
+    let temp_dir = tempfile::tempdir().ok()?;
+    let scale = format!("scale={}:-2", width);
+
+    if let Some(time) = poster_time.filter(|t| *t >= 0.0) {
+        let poster = temp_dir.path().join("poster.jpg");
+        if extract_frame_at(ffmpeg_path, path, time, Some(&scale), &poster) {
+            if let Ok(img) = image::open(&poster) {
+                return Some(img);
+            }
+        }
+    }
+
+    let (duration, framerate) = probe_video_timing(ffprobe_path, path);
+    let window = duration.unwrap_or(0.0).min(POSTER_WINDOW_SECS);
+    let n = filmstrip_frame_count(window, framerate, Some(POSTER_CANDIDATES), None);
+    let (frames, _) = sample_evenly(ffmpeg_path, path, temp_dir.path(), 0.0, window, n, &scale);
+
+    let candidates: Vec<(image::DynamicImage, FrameScore)> = frames
+        .iter()
+        .filter_map(|p| image::open(p).ok())
+        .map(|img| {
+            let score = score_frame(&img.to_luma8());
+            (img, score)
+        })
+        .collect();
+
+    // Prefer usable frames; if every candidate is dark or flat, take the
+    // brightest/most detailed one rather than nothing
+    let best_usable = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, score))| score.is_usable())
+        .max_by(|(_, (_, a)), (_, (_, b))| a.sharpness.total_cmp(&b.sharpness))
+        .map(|(i, _)| i);
+    let best = best_usable.or_else(|| {
+        candidates
+            .iter()
+            .enumerate()
+            .max_by(|(_, (_, a)), (_, (_, b))| {
+                (a.brightness * a.contrast).total_cmp(&(b.brightness * b.contrast))
+            })
+            .map(|(i, _)| i)
+    })?;
+
+    candidates.into_iter().nth(best).map(|(img, _)| img)
+}
+
+/// Extract evenly spaced frames in a single ffmpeg pass and pack them into
+/// one sprite image plus an offsets table
+fn generate_filmstrip_sync(
+    path: &str,
+    ffmpeg_path: &Path,
+    ffprobe_path: &Path,
+    count: Option<u32>,
+    interval: Option<f64>,
+    tile_width: u32,
+) -> Result<Filmstrip, String> {
+    let (duration, framerate) = probe_video_timing(ffprobe_path, path);
+    let duration = duration.unwrap_or(0.0);
+    let n = filmstrip_frame_count(duration, framerate, count, interval);
+    // An interval is kept as asked unless the frame cap forces wider spacing
+    let step = match (count, interval) {
+        (None, Some(step)) if step > 0.0 && step * n as f64 >= duration => step,
+        _ if duration > 0.0 => duration / n as f64,
+        _ => 0.0,
+    };
+
+    let strips_dir = cache_dir("filmstrips")?;
+    let key = cache_key(path, &format!("filmstrip-{}-{}-{:.3}", n, tile_width, step));
+    let sprite_name = format!("{}.jpg", key);
+    let sprite_path = strips_dir.join(&sprite_name);
+    let table_path = strips_dir.join(format!("{}.json", key));
+
+    // Cached sprite + offsets table from an earlier call
+    if sprite_path.exists() {
+        if let Some(strip) = read_cached_json::<Filmstrip>(&table_path) {
+            return Ok(strip);
+        }
+    }
+
+    let temp_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let scale = format!("scale={}:-2", tile_width);
+    let (frames, times) = sample_every(ffmpeg_path, path, temp_dir.path(), 0.0, step, n, &scale);
+
+    if frames.is_empty() {
+        return Err("Failed to extract frames".to_string());
+    }
+
+    // Decode failures drop their time too, so tiles and times stay paired
+    let images: Vec<(image::RgbImage, f64)> = frames
+        .iter()
+        .zip(times)
+        .filter_map(|(p, time)| image::open(p).ok().map(|img| (img.to_rgb8(), time)))
+        .collect();
+
+    let (tile_w, tile_h) = images
+        .first()
+        .map(|(img, _)| img.dimensions())
+        .ok_or_else(|| "Failed to decode extracted frames".to_string())?;
+
+    let columns = (images.len() as u32).min(SPRITE_COLUMNS);
+    let rows = (images.len() as u32).div_ceil(columns);
+    let mut sprite = image::RgbImage::new(tile_w * columns, tile_h * rows);
+
+    let mut table = Vec::with_capacity(images.len());
+    for (i, (img, time)) in images.iter().enumerate() {
+        let x = (i as u32 % columns) * tile_w;
+        let y = (i as u32 / columns) * tile_h;
+        image::imageops::replace(&mut sprite, img, x as i64, y as i64);
+        table.push(FilmstripFrame {
+            time: *time,
+            x,
+            y,
+            width: tile_w,
+            height: tile_h,
+        });
+    }
+
+    let (sprite_width, sprite_height) = sprite.dimensions();
+    let mut buf = Vec::new();
+    image::DynamicImage::ImageRgb8(sprite)
+        .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Jpeg)
+        .map_err(|e| format!("Failed to encode filmstrip: {}", e))?;
+    write_cache_file(&sprite_path, &buf)?;
+
+    let strip = Filmstrip {
+        sprite: cache_url("filmstrips", &sprite_name),
+        sprite_width,
+        sprite_height,
+        frames: table,
+    };
+
+    write_cached_json(&table_path, &strip);
+
+    Ok(strip)
+}
+
+/// Generate a filmstrip sprite for a video - either `count` frames spread
+/// evenly or one frame every `interval` seconds. Either way there are at most
+/// 120 frames; longer clips get wider spacing than the interval asked for.
+#[command]
+pub async fn generate_filmstrip(
+    app: AppHandle,
+    path: String,
+    count: Option<u32>,
+    interval: Option<f64>,
+    tile_width: Option<u32>,
+) -> Result<Filmstrip, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let tile_width = tile_width.unwrap_or(DEFAULT_TILE_WIDTH).clamp(32, 640);
+
+    tokio::task::spawn_blocking(move || {
+        generate_filmstrip_sync(&path, &ffmpeg_path, &ffprobe_path, count, interval, tile_width)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// The exact frame at `time` (seconds), scaled down to `max_width` if
+/// given, as a cached qcthumb:// URL
+pub fn frame_url_at(ffmpeg_path: &Path, path: &str, time: f64, max_width: Option<u32>) -> Result<String, String> {
+    let frames_dir = cache_dir("frames")?;
+    let variant = format!("frame-{:.3}-{}", time, max_width.unwrap_or(0));
+    let file_name = format!("{}.jpg", cache_key(path, &variant));
+    let frame_path = frames_dir.join(&file_name);
+
+    if frame_path.exists() {
+        return Ok(cache_url("frames", &file_name));
+    }
+
+    // Never upscale past the source width
+    let filter = max_width.map(|w| format!("scale='min({},iw)':-2", w));
+    let temp_path = temp_cache_path(&frame_path);
+    if !extract_frame_at(ffmpeg_path, path, time, filter.as_deref(), &temp_path) {
+        return Err(format!("Failed to extract frame at {:.3}s", time));
+    }
+    commit_cache_file(&temp_path, &frame_path)?;
+
+    Ok(cache_url("frames", &file_name))
+}
+
+/// Get the exact frame at `time` (seconds), scaled down to `max_width` if
+/// given, as a cached qcthumb:// URL
+#[command]
+pub async fn get_frame_at(
+    app: AppHandle,
+    path: String,
+    time: f64,
+    max_width: Option<u32>,
+) -> Result<String, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+
+    tokio::task::spawn_blocking(move || frame_url_at(&ffmpeg_path, &path, time, max_width))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Save the frame at `time` as a full-resolution photo. The format follows
+/// the output extension (PNG, otherwise high-quality JPEG).
+#[command]
+pub async fn save_frame_at(
+    app: AppHandle,
+    path: String,
+    time: f64,
+    output_path: String,
+) -> Result<String, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+
+    let ext = Path::new(&output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let final_output = match ext.as_str() {
+        "png" | "jpg" | "jpeg" => output_path,
+        _ => format!("{}.jpg", output_path),
+    };
+
+    tokio::task::spawn_blocking(move || {
+        // Extract next to the target and only replace an existing file once
+        // the new frame is safely written
+        let output = PathBuf::from(&final_output);
+        let temp_path = temp_cache_path(&output);
+        if !extract_frame_at(&ffmpeg_path, &path, time, None, &temp_path) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(format!("Failed to extract frame at {:.3}s", time));
+        }
+        std::fs::rename(&temp_path, &output).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            format!("Failed to save frame: {}", e)
+        })?;
+        Ok(final_output)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -0,0 +1,214 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::cache::{cache_dir, cache_key, write_cache_file};
+use super::capture_time::read_exif;
+
+/// Camera RAW formats - imported through their embedded JPEG preview
+pub const RAW_EXTENSIONS: &[&str] = &["dng", "cr2", "cr3", "nef", "arw", "raf", "orf", "rw2", "pef", "srw"];
+
+/// Still formats that ffmpeg can't reliably read as a `-loop 1` input
+/// (HEIC tile grids, AVIF on older builds, RAW). Export feeds ffmpeg a
+/// cached JPEG of these instead.
+const TRANSCODE_EXTENSIONS: &[&str] = &["heic", "heif", "avif"];
+
+/// Max embedded JPEG candidates checked when looking for a RAW preview
+const MAX_PREVIEW_CANDIDATES: usize = 32;
+
+/// JPEG quality for stills converted for export
+const STILL_JPEG_QUALITY: u8 = 95;
+
+pub(crate) fn extension(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default()
+}
+
+pub fn is_raw(path: &str) -> bool {
+    RAW_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+/// Whether export must convert this still before handing it to ffmpeg
+pub fn needs_transcode(path: &str) -> bool {
+    is_raw(path) || TRANSCODE_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+/// HEIF stores rotation in irot/imir boxes, which its decoders already
+/// apply; the EXIF tag there is informational only
+fn has_container_orientation(path: &str) -> bool {
+    TRANSCODE_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+/// EXIF Orientation tag (1-8), 1 when absent or not applicable
+pub fn exif_orientation(path: &str) -> u32 {
+    if has_container_orientation(path) {
+        return 1;
+    }
+    read_exif(path)
+        .and_then(|exif| {
+            exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .and_then(|f| f.value.get_uint(0))
+        })
+        .filter(|o| (1..=8).contains(o))
+        .unwrap_or(1)
+}
+
+/// Orientations 5-8 turn the picture on its side
+pub fn swaps_dimensions(orientation: u32) -> bool {
+    (5..=8).contains(&orientation)
+}
+
+/// Largest JPEG embedded in a RAW file. RAW containers (TIFF-based DNG/CR2/
+/// NEF/ARW, ISO-BMFF CR3, ...) all carry one or more full JPEG previews, so
+/// scanning for JPEG start markers works without a RAW decoder.
+fn raw_preview(path: &str) -> Option<image::DynamicImage> {
+    let data = std::fs::read(path).ok()?;
+
+    let best = data
+        .windows(3)
+        .enumerate()
+        .filter(|(_, w)| w == &[0xFF, 0xD8, 0xFF])
+        .map(|(i, _)| i)
+        .take(MAX_PREVIEW_CANDIDATES)
+        .filter_map(|start| {
+            let (w, h) = image::ImageReader::with_format(
+                std::io::Cursor::new(&data[start..]),
+                image::ImageFormat::Jpeg,
+            )
+            .into_dimensions()
+            .ok()?;
+            Some((start, w as u64 * h as u64))
+        })
+        .max_by_key(|(_, pixels)| *pixels)?;
+
+    // The decoder stops at the preview's end-of-image marker
+    image::load_from_memory_with_format(&data[best.0..], image::ImageFormat::Jpeg).ok()
+}
+
+/// macOS ImageIO via `sips` - handles HEIC grids and RAW natively
+fn decode_with_sips(path: &str) -> Option<image::DynamicImage> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+
+    let temp_dir = tempfile::tempdir().ok()?;
+    let out = temp_dir.path().join("decoded.png");
+    let status = Command::new("sips")
+        .args(["-s", "format", "png", path, "--out"])
+        .arg(&out)
+        .output()
+        .ok()?;
+
+    if !status.status.success() {
+        return None;
+    }
+    image::open(&out).ok()
+}
+
+/// Decode the first picture with ffmpeg. ffmpeg 7.1+ composes HEIF tile
+/// grids itself, so this also covers HEIC where `sips` isn't available.
+fn decode_with_ffmpeg(path: &str, ffmpeg_path: &Path) -> Option<image::DynamicImage> {
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-v", "error",
+            "-i", path,
+            "-frames:v", "1",
+            "-f", "image2pipe",
+            "-c:v", "png",
+            "-",
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return None;
+    }
+    image::load_from_memory_with_format(&output.stdout, image::ImageFormat::Png).ok()
+}
+
+/// Decode a still as stored, without applying its orientation
+fn decode_stored(path: &str, ffmpeg_path: &Path) -> Result<image::DynamicImage, String> {
+    if !needs_transcode(path) {
+        if let Ok(img) = image::open(path) {
+            return Ok(img);
+        }
+    }
+
+    if is_raw(path) {
+        if let Some(img) = raw_preview(path) {
+            return Ok(img);
+        }
+    }
+
+    decode_with_sips(path)
+        .or_else(|| decode_with_ffmpeg(path, ffmpeg_path))
+        .ok_or_else(|| format!("Failed to decode image: {}", path))
+}
+
+/// Decode any supported still, upright: the `image` crate first, then the
+/// embedded preview for RAW, then platform/ffmpeg decoders. The EXIF
+/// orientation is applied to the pixels.
+pub fn decode_image(path: &str, ffmpeg_path: &Path) -> Result<image::DynamicImage, String> {
+    let mut img = decode_stored(path, ffmpeg_path)?;
+    // RAW previews carry no EXIF of their own; the RAW file's tag applies
+    if let Some(orientation) = image::metadata::Orientation::from_exif(exif_orientation(path) as u8) {
+        img.apply_orientation(orientation);
+    }
+    Ok(img)
+}
+
+/// Displayed (oriented) pixel dimensions of a still, without a full
+/// decode where possible
+pub fn image_dimensions(path: &str, ffmpeg_path: &Path) -> Option<(u32, u32)> {
+    if !needs_transcode(path) {
+        if let Ok((w, h)) = image::image_dimensions(path) {
+            return Some(if swaps_dimensions(exif_orientation(path)) { (h, w) } else { (w, h) });
+        }
+    }
+    decode_image(path, ffmpeg_path).ok().map(|img| (img.width(), img.height()))
+}
+
+/// Path ffmpeg should read for a still, given the export frame size.
+/// Formats it can't handle, photos with an EXIF rotation/flip ffmpeg would
+/// ignore, and stills larger than the frame are converted once to an
+/// upright, frame-sized high-quality JPEG in the cache. Without this a
+/// 48 MP photo would be rescaled by ffmpeg for every output frame.
+pub fn export_source_for_still(
+    path: &str,
+    ffmpeg_path: &Path,
+    frame_width: u32,
+    frame_height: u32,
+) -> Result<String, String> {
+    if !needs_transcode(path) && exif_orientation(path) == 1 {
+        let fits = image::image_dimensions(path)
+            .is_ok_and(|(w, h)| w <= frame_width && h <= frame_height);
+        if fits {
+            return Ok(path.to_string());
+        }
+    }
+
+    let stills_dir = cache_dir("stills")?;
+    let variant = format!("still-fit-{}x{}", frame_width, frame_height);
+    let target = stills_dir.join(format!("{}.jpg", cache_key(path, &variant)));
+    if target.exists() {
+        return Ok(target.to_string_lossy().to_string());
+    }
+
+    let mut img = decode_image(path, ffmpeg_path)?;
+    if img.width() > frame_width || img.height() > frame_height {
+        // Keeps the aspect ratio; the filter graph pads to the frame
+        img = img.resize(frame_width, frame_height, image::imageops::FilterType::Lanczos3);
+    }
+
+    let mut buf = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, STILL_JPEG_QUALITY);
+    img.to_rgb8()
+        .write_with_encoder(encoder)
+        .map_err(|e| format!("Failed to encode still: {}", e))?;
+    write_cache_file(&target, &buf)?;
+
+    Ok(target.to_string_lossy().to_string())
+}
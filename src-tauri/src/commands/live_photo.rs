@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use super::capture_time::read_exif;
+use super::images::extension;
+use super::metadata::{generate_thumbnail_sync, get_metadata_fast, MediaMetadata};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+/// Live Photo motion clips run about 3 seconds; anything much longer with
+/// the same name is an ordinary video
+const MAX_MOTION_DURATION: f64 = 5.0;
+
+const STILL_EXTENSIONS: &[&str] = &["heic", "heif", "jpg", "jpeg"];
+const MOTION_EXTENSIONS: &[&str] = &["mov"];
+
+/// How a Live Photo plays on the timeline
+pub const MODE_STILL: &str = "still";
+pub const MODE_MOTION: &str = "motion";
+pub const MODE_STILL_WITH_INTRO: &str = "still_with_motion_intro";
+
+/// A still and the motion clip shot with it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivePhoto {
+    pub still_path: String,
+    pub motion_path: String,
+    pub motion_duration: f64,
+    pub content_identifier: Option<String>, // shared by both files when present
+}
+
+/// Result of pairing imported files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivePhotoScan {
+    pub live_photos: Vec<LivePhoto>,
+    pub other_paths: Vec<String>, // everything not part of a pair
+}
+
+/// The motion half of a Live Photo timeline item, and which half plays.
+/// `mode` is "still", "motion" or "still_with_motion_intro".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivePhotoSource {
+    pub motion_path: String,
+    pub motion_duration: f64,
+    pub mode: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MotionTags {
+    #[serde(rename = "com.apple.quicktime.content.identifier")]
+    content_identifier: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MotionFormat {
+    duration: Option<String>,
+    tags: Option<MotionTags>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MotionProbe {
+    format: Option<MotionFormat>,
+}
+
+/// Directory plus lower-cased stem - IMG_1234.HEIC and IMG_1234.MOV match
+fn base_key(path: &str) -> String {
+    let p = Path::new(path);
+    let dir = p.parent().map(|d| d.to_string_lossy().to_string()).unwrap_or_default();
+    let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+    format!("{}/{}", dir, stem)
+}
+
+/// First UUID-shaped ASCII string in `bytes`
+fn find_uuid(bytes: &[u8]) -> Option<String> {
+    bytes
+        .windows(36)
+        .find(|w| {
+            w.iter().enumerate().all(|(i, b)| match i {
+                8 | 13 | 18 | 23 => *b == b'-',
+                _ => b.is_ascii_hexdigit(),
+            })
+        })
+        .map(|w| String::from_utf8_lossy(w).to_uppercase())
+}
+
+/// The still's content identifier lives in Apple's MakerNote, which
+/// kamadak-exif leaves as raw bytes
+fn still_content_identifier(path: &str) -> Option<String> {
+    let exif = read_exif(path)?;
+    match &exif.get_field(exif::Tag::MakerNote, exif::In::PRIMARY)?.value {
+        exif::Value::Undefined(bytes, _) => find_uuid(bytes),
+        _ => None,
+    }
+}
+
+/// Duration and content identifier of a candidate motion clip
+fn probe_motion(ffprobe_path: &Path, path: &str) -> Option<(f64, Option<String>)> {
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-show_entries", "format=duration:format_tags=com.apple.quicktime.content.identifier",
+            path,
+        ])
+        .output()
+        .ok()?;
+
+    let format = serde_json::from_slice::<MotionProbe>(&output.stdout).ok()?.format?;
+    let duration = format.duration.as_deref()?.parse::<f64>().ok()?;
+    let identifier = format
+        .tags
+        .and_then(|t| t.content_identifier)
+        .map(|id| id.to_uppercase());
+    Some((duration, identifier))
+}
+
+/// Pair stills with their motion clips: by content identifier when both
+/// files carry one, otherwise by file name in the same folder
+fn pair_live_photos(ffprobe_path: &Path, paths: &[String]) -> LivePhotoScan {
+    let (stills, rest): (Vec<&String>, Vec<&String>) = paths
+        .iter()
+        .partition(|p| STILL_EXTENSIONS.contains(&extension(p).as_str()));
+
+    // Short MOVs only - probing is cheap next to a full metadata pass
+    let motions: Vec<(&String, f64, Option<String>)> = rest
+        .iter()
+        .filter(|p| MOTION_EXTENSIONS.contains(&extension(p).as_str()))
+        .filter_map(|p| {
+            let (duration, id) = probe_motion(ffprobe_path, p)?;
+            (duration <= MAX_MOTION_DURATION).then_some((*p, duration, id))
+        })
+        .collect();
+    if motions.is_empty() {
+        return LivePhotoScan { live_photos: Vec::new(), other_paths: paths.to_vec() };
+    }
+
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    for (i, (path, _, id)) in motions.iter().enumerate() {
+        if let Some(id) = id {
+            by_id.insert(id.as_str(), i);
+        }
+        by_name.insert(base_key(path), i);
+    }
+
+    let mut used = vec![false; motions.len()];
+    let mut live_photos = Vec::new();
+    let mut paired_stills = Vec::new();
+    for still in stills {
+        let still_id = still_content_identifier(still);
+        let found = still_id
+            .as_deref()
+            .and_then(|id| by_id.get(id))
+            .or_else(|| by_name.get(&base_key(still)))
+            .copied()
+            .filter(|i| !used[*i]);
+        let Some(i) = found else { continue };
+
+        used[i] = true;
+        let (motion_path, motion_duration, motion_id) = &motions[i];
+        live_photos.push(LivePhoto {
+            still_path: still.clone(),
+            motion_path: (*motion_path).clone(),
+            motion_duration: *motion_duration,
+            content_identifier: still_id.or_else(|| motion_id.clone()),
+        });
+        paired_stills.push(still.as_str());
+    }
+
+    let other_paths = paths
+        .iter()
+        .filter(|p| !paired_stills.contains(&p.as_str()))
+        .filter(|p| !live_photos.iter().any(|lp| &lp.motion_path == *p))
+        .cloned()
+        .collect();
+
+    LivePhotoScan { live_photos, other_paths }
+}
+
+/// Find Live Photos (still + short MOV) among imported files
+#[command]
+pub async fn find_live_photos(app: AppHandle, paths: Vec<String>) -> Result<LivePhotoScan, String> {
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    tokio::task::spawn_blocking(move || pair_live_photos(&ffprobe_path, &paths))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))
+}
+
+/// Metadata for a Live Photo as one timeline item: the still's, with the
+/// motion clip attached. Starts out playing as the still.
+#[command]
+pub async fn get_live_photo_metadata(app: AppHandle, live_photo: LivePhoto) -> Result<MediaMetadata, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut metadata = get_metadata_fast(live_photo.still_path.clone(), &ffprobe_path, &ffmpeg_path)?;
+        metadata.thumbnail =
+            generate_thumbnail_sync(&live_photo.still_path, "image", &ffmpeg_path, &ffprobe_path, None);
+        metadata.live_photo = Some(LivePhotoSource {
+            motion_path: live_photo.motion_path,
+            motion_duration: live_photo.motion_duration,
+            mode: MODE_STILL.to_string(),
+        });
+        Ok(metadata)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -1,27 +1,77 @@
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::process::Command;
 use tauri::{command, AppHandle};
 use tokio::task::JoinSet;
 
+use super::animation::probe_animation;
+use super::black_bars::cached_content_rect;
+use super::cache::{cache_dir, cache_key, cache_url, write_cache_file};
+use super::capture_time::resolve_capture_time;
+use super::frames::{parse_rational, select_poster_frame};
+use super::detect::{sniff_media, Sniffed};
+use super::images::{decode_image, exif_orientation, image_dimensions, is_raw, swaps_dimensions};
+use super::live_photo::LivePhotoSource;
+use super::sequence::SequenceInput;
 use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+use super::transform::CropRect;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MediaMetadata {
     pub path: String,
     pub name: String,
     pub media_type: String, // "video" or "image"
+    pub is_animated: bool,  // animated GIF/WebP/PNG, reported as "video"
     pub duration: f64,      // seconds
-    pub width: u32,
+    pub width: u32,         // as displayed, after EXIF orientation
     pub height: u32,
-    pub timestamp: i64,     // file creation timestamp in milliseconds
-    pub thumbnail: Option<String>, // base64 thumbnail - lazy loaded
-    pub framerate: Option<f64>,    // frames per second
+    pub orientation: u32,   // EXIF orientation 1-8 (1 = upright)
+    pub timestamp: i64,     // capture timestamp in milliseconds
+    pub timestamp_source: String, // see capture_time::SOURCE_* - "quicktime", "exif"...
+    pub thumbnail: Option<String>, // qcthumb:// URL - lazy loaded
+    pub framerate: Option<f64>,    // frames per second (r_frame_rate)
     pub bitrate: Option<u64>,      // bits per second
+    pub avg_framerate: Option<f64>, // average frames per second
+    pub is_vfr: Option<bool>,       // Some(true) when rates disagree; None = checked at export
+    pub frame_count: Option<u32>,   // animated images and image sequences
+    pub video_codec: Option<String>,   // e.g. "h264", "hevc", "prores"
+    pub video_profile: Option<String>, // e.g. "Main 10"
+    pub pixel_format: Option<String>,  // e.g. "yuv420p10le"
+    pub bit_depth: Option<u32>,
+    pub color_space: Option<String>,     // e.g. "bt709", "bt2020nc"
+    pub color_transfer: Option<String>,  // e.g. "arib-std-b67" (HLG)
+    pub color_primaries: Option<String>,
+    pub field_order: Option<String>,    // "progressive", "tt" (top first), "bb"...
+    pub is_interlaced: bool,
+    pub pixel_aspect_ratio: f64,        // 1.0 = square; width * this = display width
+    pub has_audio: bool,
+    pub audio_codec: Option<String>,
+    pub audio_channels: Option<u32>,
+    pub audio_channel_layout: Option<String>,
+    pub audio_sample_rate: Option<u32>,
+    pub streams: Vec<StreamInfo>,
+    pub image_sequence: Option<SequenceInput>, // numbered stills played as one clip
+    pub live_photo: Option<LivePhotoSource>,    // motion clip paired with this still
+    pub content_rect: Option<CropRect>,         // picture inside burned-in black bars, once detected
+}
+
+/// One entry of the file's stream inventory
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamInfo {
+    pub index: u32,
+    pub codec_type: String, // "video", "audio", "subtitle", "data"...
+    pub codec_name: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub channels: Option<u32>,
+    pub language: Option<String>,
+    pub is_attached_pic: bool, // embedded cover art, not real video
+    pub is_default: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct FFProbeFormat {
+    format_name: Option<String>,
     duration: Option<String>,
     bit_rate: Option<String>,
     tags: Option<FFProbeTags>,
@@ -30,15 +80,60 @@ struct FFProbeFormat {
 #[derive(Debug, Serialize, Deserialize)]
 struct FFProbeTags {
     creation_time: Option<String>,
+    #[serde(rename = "com.apple.quicktime.creationdate")]
+    apple_creation_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct FFProbeDisposition {
+    #[serde(default)]
+    default: u8,
+    #[serde(default)]
+    attached_pic: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FFProbeStreamTags {
+    language: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct FFProbeStream {
+    index: Option<u32>,
     width: Option<u32>,
     height: Option<u32>,
     codec_type: Option<String>,
+    codec_name: Option<String>,
+    profile: Option<String>,
+    pix_fmt: Option<String>,
+    bits_per_raw_sample: Option<String>,
+    color_space: Option<String>,
+    color_transfer: Option<String>,
+    color_primaries: Option<String>,
+    field_order: Option<String>,   // "progressive", "tt", "bb", "tb", "bt"
+    sample_aspect_ratio: Option<String>, // e.g. "16:11" for anamorphic DV
     r_frame_rate: Option<String>,  // e.g., "30000/1001" for 29.97fps
+    avg_frame_rate: Option<String>,
     bit_rate: Option<String>,
+    channels: Option<u32>,
+    channel_layout: Option<String>,
+    sample_rate: Option<String>,
+    disposition: Option<FFProbeDisposition>,
+    tags: Option<FFProbeStreamTags>,
+}
+
+impl FFProbeStream {
+    fn is_type(&self, codec_type: &str) -> bool {
+        self.codec_type.as_deref() == Some(codec_type)
+    }
+
+    fn is_attached_pic(&self) -> bool {
+        self.disposition.as_ref().is_some_and(|d| d.attached_pic == 1)
+    }
+
+    fn is_default(&self) -> bool {
+        self.disposition.as_ref().is_some_and(|d| d.default == 1)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,17 +142,131 @@ struct FFProbeOutput {
     streams: Option<Vec<FFProbeStream>>,
 }
 
-fn get_media_type(path: &str) -> &str {
-    let ext = Path::new(path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
-
-    match ext.as_str() {
-        "mp4" | "mov" | "avi" | "mkv" | "webm" => "video",
-        "jpg" | "jpeg" | "png" | "webp" | "gif" => "image",
-        _ => "unknown",
+/// Relative gap between r_frame_rate and avg_frame_rate that means VFR
+const VFR_RATE_TOLERANCE: f64 = 0.01;
+/// Seconds of packets read when checking frame timing
+const VFR_SAMPLE_SECS: u32 = 10;
+/// A frame interval this far (relative) from the median is irregular...
+const VFR_INTERVAL_TOLERANCE: f64 = 0.25;
+/// ...and this share of irregular intervals makes a clip VFR
+const VFR_IRREGULAR_SHARE: f64 = 0.02;
+
+/// Whether the first seconds of video packets arrive at uneven intervals
+pub fn has_irregular_frame_timing(ffprobe_path: &Path, path: &str) -> bool {
+    let Ok(output) = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-select_streams", "v:0",
+            "-read_intervals", &format!("%+{}", VFR_SAMPLE_SECS),
+            "-show_entries", "packet=pts_time",
+            "-of", "csv=p=0",
+            path,
+        ])
+        .output()
+    else {
+        return false;
+    };
+
+    // Packets come in decode order; B-frames need sorting back
+    let mut pts: Vec<f64> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| l.trim().trim_end_matches(',').parse::<f64>().ok())
+        .collect();
+    pts.sort_by(f64::total_cmp);
+
+    let mut intervals: Vec<f64> = pts.windows(2).map(|w| w[1] - w[0]).filter(|d| *d > 0.0).collect();
+    if intervals.len() < 10 {
+        return false;
+    }
+    intervals.sort_by(f64::total_cmp);
+    let median = intervals[intervals.len() / 2];
+
+    let irregular = intervals
+        .iter()
+        .filter(|d| (*d - median).abs() > median * VFR_INTERVAL_TOLERANCE)
+        .count();
+    irregular as f64 / intervals.len() as f64 > VFR_IRREGULAR_SHARE
+}
+
+/// Field orders that mean the frames are woven from two fields
+pub fn is_interlaced(field_order: Option<&str>) -> bool {
+    matches!(field_order, Some("tt" | "bb" | "tb" | "bt"))
+}
+
+/// Pixel aspect ratio from ffprobe's "16:11" form; unknown ("0:1") is square
+fn pixel_aspect_ratio(sample_aspect_ratio: Option<&str>) -> f64 {
+    sample_aspect_ratio
+        .and_then(|sar| parse_rational(&sar.replace(':', "/")))
+        .filter(|par| *par > 0.0)
+        .unwrap_or(1.0)
+}
+
+/// Field order and pixel aspect ratio of the first video stream, for
+/// sources that reach export without them
+pub fn probe_scan_format(ffprobe_path: &Path, path: &str) -> (Option<String>, f64) {
+    let stream = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-select_streams", "v:0",
+            "-show_streams",
+            path,
+        ])
+        .output()
+        .ok()
+        .and_then(|o| serde_json::from_slice::<FFProbeOutput>(&o.stdout).ok())
+        .and_then(|p| p.streams)
+        .and_then(|s| s.into_iter().next());
+
+    match stream {
+        Some(s) => (s.field_order.clone(), pixel_aspect_ratio(s.sample_aspect_ratio.as_deref())),
+        None => (None, 1.0),
+    }
+}
+
+/// Decide video vs image from the sniffed magic bytes, confirmed against
+/// what ffprobe found - or for stills ffprobe can't read, by `decodes`.
+/// Returns (media_type, is_animated) or a specific rejection reason.
+fn classify_media(
+    sniffed: Option<Sniffed>,
+    probe: &FFProbeOutput,
+    decodes: impl Fn() -> bool,
+) -> Result<(&'static str, bool), String> {
+    let streams = probe.streams.as_deref().unwrap_or(&[]);
+    let has_video = streams.iter().any(|s| s.is_type("video") && !s.is_attached_pic());
+    let has_audio = streams.iter().any(|s| s.is_type("audio"));
+
+    let no_video = || {
+        if has_audio {
+            "Unsupported media type: audio-only file".to_string()
+        } else {
+            "Unsupported media type: no video or image stream".to_string()
+        }
+    };
+
+    match sniffed {
+        // ffprobe can't read RAW (and some HEIC) - the image decoder can
+        Some(Sniffed::Image(_)) if has_video || decodes() => Ok(("image", false)),
+        Some(Sniffed::Image(kind)) => Err(format!("Unsupported media type: damaged or unreadable {} image", kind)),
+        // Animated images play as clips
+        Some(Sniffed::AnimatedImage(_)) => Ok(("video", true)),
+        Some(Sniffed::Video(_)) if has_video => Ok(("video", false)),
+        Some(Sniffed::Video(_)) => Err(no_video()),
+        None => {
+            let Some(format) = probe.format.as_ref() else {
+                return Err("Unsupported media type: unrecognised file format".to_string());
+            };
+            if !has_video {
+                return Err(no_video());
+            }
+            // Single-picture demuxers mean a still ffprobe recognised
+            let format_name = format.format_name.as_deref().unwrap_or("");
+            if format_name.contains("image2") || format_name.ends_with("_pipe") {
+                Ok(("image", false))
+            } else {
+                Ok(("video", false))
+            }
+        }
     }
 }
 
@@ -69,52 +278,93 @@ fn get_file_name(path: &str) -> String {
         .to_string()
 }
 
-fn get_file_timestamp(path: &str) -> i64 {
-    std::fs::metadata(path)
-        .ok()
-        .and_then(|m| m.created().ok())
-        .map(|t| {
-            t.duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_millis() as i64)
-                .unwrap_or(0)
-        })
-        .unwrap_or(0)
+/// Bit depth implied by an ffmpeg pixel format name, e.g. "yuv420p10le" -> 10.
+/// Only planar "...p<bits>" and gray formats carry a per-component depth
+/// suffix; "nv12" or "rgb24" don't.
+fn bit_depth_from_pix_fmt(pix_fmt: &str) -> u32 {
+    let base = pix_fmt.trim_end_matches("le").trim_end_matches("be");
+    let split = base
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let (prefix, digits) = base.split_at(split);
+
+    match digits.parse::<u32>() {
+        Ok(bits) if (9..=16).contains(&bits) && (prefix.ends_with('p') || prefix.starts_with("gray")) => bits,
+        _ => 8,
+    }
+}
+
+fn stream_info(stream: &FFProbeStream) -> StreamInfo {
+    StreamInfo {
+        index: stream.index.unwrap_or(0),
+        codec_type: stream.codec_type.clone().unwrap_or_else(|| "unknown".to_string()),
+        codec_name: stream.codec_name.clone(),
+        width: stream.width,
+        height: stream.height,
+        channels: stream.channels,
+        language: stream.tags.as_ref().and_then(|t| t.language.clone()),
+        is_attached_pic: stream.is_attached_pic(),
+        is_default: stream.is_default(),
+    }
 }
 
-/// Generate thumbnail - called lazily via separate command
-fn generate_thumbnail_sync(path: &str, media_type: &str, ffmpeg_path: &PathBuf) -> Option<String> {
+/// Generate thumbnail - called lazily via separate command.
+/// Thumbnails are written to the disk cache and returned as short
+/// `qcthumb://` URLs instead of base64 data URLs. Videos use a scored
+/// representative frame unless `poster_time` is set.
+pub fn generate_thumbnail_sync(
+    path: &str,
+    media_type: &str,
+    ffmpeg_path: &Path,
+    ffprobe_path: &Path,
+    poster_time: Option<f64>,
+) -> Option<String> {
+    let thumbs_dir = cache_dir("thumbs").ok()?;
+    let variant = match poster_time {
+        Some(t) => format!("thumb-upright-200-at-{:.3}", t),
+        None => "thumb-upright-200-auto".to_string(),
+    };
+    let file_name = format!("{}.jpg", cache_key(path, &variant));
+    let thumb_path = thumbs_dir.join(&file_name);
+
+    // Already generated for this version of the file
+    if thumb_path.exists() {
+        return Some(cache_url("thumbs", &file_name));
+    }
+
     if media_type == "image" {
-        if let Ok(img) = image::open(path) {
+        if let Ok(img) = decode_image(path, ffmpeg_path) {
             let thumbnail = img.thumbnail(200, 200);
             let mut buf = Vec::new();
             if thumbnail
+                .to_rgb8()
                 .write_to(
                     &mut std::io::Cursor::new(&mut buf),
                     image::ImageFormat::Jpeg,
                 )
                 .is_ok()
+                && write_cache_file(&thumb_path, &buf).is_ok()
             {
-                return Some(format!("data:image/jpeg;base64,{}", base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &buf)));
+                return Some(cache_url("thumbs", &file_name));
             }
         }
     } else if media_type == "video" {
-        let temp_path = std::env::temp_dir().join(format!("thumb_{}.jpg", uuid::Uuid::new_v4()));
-
-        let output = Command::new(ffmpeg_path)
-            .args([
-                "-i", path,
-                "-ss", "00:00:01",
-                "-vframes", "1",
-                "-vf", "scale=200:-1",
-                "-y",
-                temp_path.to_str().unwrap_or(""),
-            ])
-            .output();
-
-        if output.is_ok() {
-            if let Ok(data) = std::fs::read(&temp_path) {
-                let _ = std::fs::remove_file(&temp_path);
-                return Some(format!("data:image/jpeg;base64,{}", base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &data)));
+        // Animated WebP isn't decodable by every ffmpeg build; the image
+        // crate gives us its first frame instead
+        let frame = select_poster_frame(ffmpeg_path, ffprobe_path, path, poster_time, 200)
+            .or_else(|| decode_image(path, ffmpeg_path).ok().map(|img| img.thumbnail(200, 200)));
+        if let Some(frame) = frame {
+            let mut buf = Vec::new();
+            if frame
+                .to_rgb8()
+                .write_to(
+                    &mut std::io::Cursor::new(&mut buf),
+                    image::ImageFormat::Jpeg,
+                )
+                .is_ok()
+                && write_cache_file(&thumb_path, &buf).is_ok()
+            {
+                return Some(cache_url("thumbs", &file_name));
             }
         }
     }
@@ -122,15 +372,10 @@ fn generate_thumbnail_sync(path: &str, media_type: &str, ffmpeg_path: &PathBuf)
 }
 
 /// Extract metadata only (no thumbnail) - fast
-fn get_metadata_fast(path: String, ffprobe_path: &PathBuf) -> Result<MediaMetadata, String> {
-    let media_type = get_media_type(&path).to_string();
-
-    if media_type == "unknown" {
-        return Err("Unsupported media type".to_string());
-    }
-
+pub fn get_metadata_fast(path: String, ffprobe_path: &Path, ffmpeg_path: &Path) -> Result<MediaMetadata, String> {
+    // Magic bytes first - extensions are often missing or wrong
+    let sniffed = sniff_media(&path)?;
     let name = get_file_name(&path);
-    let timestamp = get_file_timestamp(&path);
 
     // Get dimensions and duration using ffprobe
     let output = Command::new(ffprobe_path)
@@ -147,34 +392,98 @@ fn get_metadata_fast(path: String, ffprobe_path: &PathBuf) -> Result<MediaMetada
     let probe_output: FFProbeOutput = serde_json::from_slice(&output.stdout)
         .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
 
-    // Find the video stream
-    let video_stream = probe_output
-        .streams
-        .as_ref()
-        .and_then(|streams| {
-            streams.iter().find(|s| {
-                s.codec_type.as_ref().map_or(false, |t| t == "video")
-            })
-        });
+    let (media_type, is_animated) = classify_media(sniffed, &probe_output, || {
+        image_dimensions(&path, ffmpeg_path).is_some()
+    })?;
+    let media_type = media_type.to_string();
+
+    let format_tags = probe_output.format.as_ref().and_then(|f| f.tags.as_ref());
+    let capture = resolve_capture_time(
+        &path,
+        format_tags.and_then(|t| t.apple_creation_date.as_deref()),
+        format_tags.and_then(|t| t.creation_time.as_deref()),
+    );
+
+    let streams = probe_output.streams.as_deref().unwrap_or(&[]);
+
+    // Primary video stream: skip attached pictures (MP4/M4A cover art),
+    // prefer the default-disposition stream
+    let video_streams: Vec<&FFProbeStream> = streams
+        .iter()
+        .filter(|s| s.is_type("video") && !s.is_attached_pic())
+        .collect();
+    let video_stream = video_streams
+        .iter()
+        .find(|s| s.is_default())
+        .or_else(|| video_streams.first())
+        .copied();
+
+    let audio_streams: Vec<&FFProbeStream> = streams.iter().filter(|s| s.is_type("audio")).collect();
+    let audio_stream = audio_streams
+        .iter()
+        .find(|s| s.is_default())
+        .or_else(|| audio_streams.first())
+        .copied();
 
     // Extract dimensions
-    let (width, height) = video_stream
+    let (mut width, mut height) = video_stream
         .map(|s| (s.width.unwrap_or(0), s.height.unwrap_or(0)))
         .unwrap_or((0, 0));
 
-    // Extract framerate
-    let framerate = video_stream
-        .and_then(|s| s.r_frame_rate.as_ref())
-        .and_then(|fps| {
-            let parts: Vec<&str> = fps.split('/').collect();
-            if parts.len() == 2 {
-                let num = parts[0].parse::<f64>().ok()?;
-                let den = parts[1].parse::<f64>().ok()?;
-                if den > 0.0 { Some(num / den) } else { None }
-            } else {
-                fps.parse::<f64>().ok()
-            }
-        });
+    // ffprobe can't size RAW files (we use their embedded preview) and
+    // reports nothing for some stills - ask the image decoder instead.
+    // Either way stills are reported as displayed: a portrait photo stored
+    // landscape with an EXIF rotation has its width and height swapped.
+    let orientation = if media_type == "image" { exif_orientation(&path) } else { 1 };
+    if media_type == "image" && (width == 0 || is_raw(&path)) {
+        if let Some((w, h)) = image_dimensions(&path, ffmpeg_path) {
+            width = w;
+            height = h;
+        }
+    } else if swaps_dimensions(orientation) {
+        std::mem::swap(&mut width, &mut height);
+    } else if is_animated && width == 0 {
+        // ffprobe has no animated WebP demuxer on older builds
+        if let Ok((w, h)) = image::image_dimensions(&path) {
+            width = w;
+            height = h;
+        }
+    }
+
+    // Extract framerate - r_frame_rate is the stream's base rate, while
+    // avg_frame_rate reflects what actually plays; they differ for VFR
+    let mut framerate = video_stream
+        .and_then(|s| s.r_frame_rate.as_deref())
+        .and_then(parse_rational);
+    let mut avg_framerate = video_stream
+        .and_then(|s| s.avg_frame_rate.as_deref())
+        .and_then(parse_rational)
+        .filter(|f| *f > 0.0);
+    // Agreeing rates don't rule VFR out - phones report a nominal rate -
+    // but scanning packets for every import is too slow, so export checks
+    // the timing of the clips where it matters
+    let rates_disagree = matches!(
+        (framerate, avg_framerate),
+        (Some(r), Some(a)) if (r - a).abs() > r * VFR_RATE_TOLERANCE
+    );
+    let mut is_vfr = (media_type == "video" && !is_animated && rates_disagree).then_some(true);
+
+    // Animated images: ffprobe's timing is missing (WebP) or rounded to
+    // the GIF tick, so count the frames and add up their delays ourselves
+    let animation = if is_animated { probe_animation(&path) } else { None };
+    if let Some(anim) = &animation {
+        let fps = anim.frame_count as f64 / anim.duration;
+        framerate = Some(fps);
+        avg_framerate = Some(fps);
+        is_vfr = Some(anim.is_vfr);
+    }
+
+    let bit_depth = video_stream.and_then(|s| {
+        s.bits_per_raw_sample
+            .as_deref()
+            .and_then(|b| b.parse::<u32>().ok())
+            .or_else(|| s.pix_fmt.as_deref().map(bit_depth_from_pix_fmt))
+    });
 
     // Extract bitrate
     let bitrate = video_stream
@@ -191,26 +500,58 @@ fn get_metadata_fast(path: String, ffprobe_path: &PathBuf) -> Result<MediaMetada
     // Extract duration
     let duration = if media_type == "image" {
         4.0
+    } else if let Some(anim) = &animation {
+        anim.duration
     } else {
         probe_output
             .format
             .as_ref()
             .and_then(|f| f.duration.as_ref())
             .and_then(|d| d.parse::<f64>().ok())
-            .unwrap_or(0.0)
+            // Animated images ffprobe can't time still need a clip length
+            .unwrap_or(if is_animated { 4.0 } else { 0.0 })
     };
 
+    let content_rect = cached_content_rect(&path);
+
     Ok(MediaMetadata {
         path,
         name,
         media_type,
+        is_animated,
         duration,
         width,
         height,
-        timestamp,
+        orientation,
+        timestamp: capture.timestamp,
+        timestamp_source: capture.source.to_string(),
         thumbnail: None, // Lazy loaded later
         framerate,
         bitrate,
+        avg_framerate,
+        is_vfr,
+        frame_count: animation.as_ref().map(|a| a.frame_count),
+        video_codec: video_stream.and_then(|s| s.codec_name.clone()),
+        video_profile: video_stream.and_then(|s| s.profile.clone()),
+        pixel_format: video_stream.and_then(|s| s.pix_fmt.clone()),
+        bit_depth,
+        color_space: video_stream.and_then(|s| s.color_space.clone()),
+        color_transfer: video_stream.and_then(|s| s.color_transfer.clone()),
+        color_primaries: video_stream.and_then(|s| s.color_primaries.clone()),
+        field_order: video_stream.and_then(|s| s.field_order.clone()),
+        is_interlaced: is_interlaced(video_stream.and_then(|s| s.field_order.as_deref())),
+        pixel_aspect_ratio: pixel_aspect_ratio(video_stream.and_then(|s| s.sample_aspect_ratio.as_deref())),
+        has_audio: audio_stream.is_some(),
+        audio_codec: audio_stream.and_then(|s| s.codec_name.clone()),
+        audio_channels: audio_stream.and_then(|s| s.channels),
+        audio_channel_layout: audio_stream.and_then(|s| s.channel_layout.clone()),
+        audio_sample_rate: audio_stream
+            .and_then(|s| s.sample_rate.as_deref())
+            .and_then(|r| r.parse::<u32>().ok()),
+        streams: streams.iter().map(stream_info).collect(),
+        image_sequence: None,
+        live_photo: None,
+        content_rect,
     })
 }
 
@@ -221,79 +562,117 @@ pub async fn get_media_metadata(app: AppHandle, path: String) -> Result<MediaMet
 
     // Run in blocking thread to not block async runtime
     tokio::task::spawn_blocking(move || {
-        let mut metadata = get_metadata_fast(path.clone(), &ffprobe_path)?;
+        let mut metadata = get_metadata_fast(path.clone(), &ffprobe_path, &ffmpeg_path)?;
         // Generate thumbnail synchronously for single file
-        metadata.thumbnail = generate_thumbnail_sync(&path, &metadata.media_type, &ffmpeg_path);
+        metadata.thumbnail = generate_thumbnail_sync(&path, &metadata.media_type, &ffmpeg_path, &ffprobe_path, None);
         Ok(metadata)
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
-/// Parallel batch metadata extraction (#2 optimization)
+/// A file the batch couldn't import, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataBatch {
+    pub items: Vec<MediaMetadata>,
+    pub rejected: Vec<RejectedFile>,
+}
+
+/// Parallel batch metadata extraction (#2 optimization). Files that can't
+/// be imported come back in `rejected` with the reason.
 #[command]
-pub async fn get_media_metadata_batch(app: AppHandle, paths: Vec<String>) -> Result<Vec<MediaMetadata>, String> {
+pub async fn get_media_metadata_batch(app: AppHandle, paths: Vec<String>) -> Result<MetadataBatch, String> {
     let ffprobe_path = get_ffprobe_path(&app)?;
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
 
     // Use JoinSet for parallel execution
-    let mut join_set: JoinSet<Result<MediaMetadata, String>> = JoinSet::new();
+    let mut join_set: JoinSet<(String, Result<MediaMetadata, String>)> = JoinSet::new();
 
     // Spawn all metadata extraction tasks in parallel
     for path in paths {
         let ffprobe = ffprobe_path.clone();
+        let ffmpeg = ffmpeg_path.clone();
         join_set.spawn(async move {
-            tokio::task::spawn_blocking(move || get_metadata_fast(path, &ffprobe))
+            let probe_path = path.clone();
+            let result = tokio::task::spawn_blocking(move || get_metadata_fast(probe_path, &ffprobe, &ffmpeg))
                 .await
-                .map_err(|e| format!("Task failed: {}", e))?
+                .map_err(|e| format!("Task failed: {}", e))
+                .and_then(|r| r);
+            (path, result)
         });
     }
 
     // Collect results as they complete
-    let mut results = Vec::new();
+    let mut items = Vec::new();
+    let mut rejected = Vec::new();
     while let Some(result) = join_set.join_next().await {
         match result {
-            Ok(Ok(metadata)) => results.push(metadata),
-            Ok(Err(e)) => log::warn!("Failed to get metadata: {}", e),
+            Ok((_, Ok(metadata))) => items.push(metadata),
+            Ok((path, Err(reason))) => {
+                log::warn!("Failed to get metadata for {}: {}", path, reason);
+                rejected.push(RejectedFile { path, reason });
+            }
             Err(e) => log::warn!("Task panicked: {}", e),
         }
     }
 
     // Sort by timestamp to maintain order
-    results.sort_by_key(|m| m.timestamp);
+    items.sort_by_key(|m| m.timestamp);
+    rejected.sort_by(|a, b| a.path.cmp(&b.path));
 
-    Ok(results)
+    Ok(MetadataBatch { items, rejected })
 }
 
-/// Lazy thumbnail generation - called separately after metadata (#4 optimization)
+/// Lazy thumbnail generation - called separately after metadata (#4 optimization).
+/// `poster_time` pins a video thumbnail to a user-chosen frame.
 #[command]
-pub async fn generate_thumbnail(app: AppHandle, path: String, media_type: String) -> Result<Option<String>, String> {
+pub async fn generate_thumbnail(
+    app: AppHandle,
+    path: String,
+    media_type: String,
+    poster_time: Option<f64>,
+) -> Result<Option<String>, String> {
     let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
 
     tokio::task::spawn_blocking(move || {
-        Ok(generate_thumbnail_sync(&path, &media_type, &ffmpeg_path))
+        Ok(generate_thumbnail_sync(&path, &media_type, &ffmpeg_path, &ffprobe_path, poster_time))
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
-/// Batch thumbnail generation - parallel
+/// Batch thumbnail generation - parallel. Each item is (path, media type,
+/// poster time); results carry the poster time back so items sharing a path
+/// can be told apart.
 #[command]
-pub async fn generate_thumbnails_batch(app: AppHandle, items: Vec<(String, String)>) -> Result<Vec<(String, Option<String>)>, String> {
+pub async fn generate_thumbnails_batch(
+    app: AppHandle,
+    items: Vec<(String, String, Option<f64>)>,
+) -> Result<Vec<(String, Option<f64>, Option<String>)>, String> {
     let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
 
-    let mut join_set: JoinSet<(String, Option<String>)> = JoinSet::new();
+    let mut join_set: JoinSet<(String, Option<f64>, Option<String>)> = JoinSet::new();
 
-    for (path, media_type) in items {
+    for (path, media_type, poster_time) in items {
         let path_clone = path.clone();
         let ffmpeg = ffmpeg_path.clone();
+        let ffprobe = ffprobe_path.clone();
         join_set.spawn(async move {
             let thumbnail = tokio::task::spawn_blocking(move || {
-                generate_thumbnail_sync(&path, &media_type, &ffmpeg)
+                generate_thumbnail_sync(&path, &media_type, &ffmpeg, &ffprobe, poster_time)
             })
             .await
             .ok()
             .flatten();
-            (path_clone, thumbnail)
+            (path_clone, poster_time, thumbnail)
         });
     }
 
@@ -1,4 +1,21 @@
+pub mod animation;
+pub mod black_bars;
+pub mod cache;
+pub mod capture_time;
+pub mod detect;
 pub mod ffmpeg;
 pub mod files;
+pub mod frames;
+pub mod images;
+pub mod live_photo;
 pub mod metadata;
+pub mod preview;
+pub mod protocol;
+pub mod proxy;
+pub mod reframe;
+pub mod scenes;
+pub mod sequence;
 pub mod sidecar;
+pub mod silence;
+pub mod transform;
+pub mod waveform;
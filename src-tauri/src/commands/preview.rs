@@ -0,0 +1,263 @@
+use std::path::Path;
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, cache_url, commit_cache_file, content_key, temp_cache_path};
+use super::ffmpeg::{build_filter_graph, prepare_export_sources, CoverConfig, ExportConfig, MediaItem};
+use super::sidecar::run_ffmpeg_command;
+
+/// Longest side of a low-resolution preview clip
+const PREVIEW_CLIP_MAX_DIMENSION: u32 = 640;
+const PREVIEW_CLIP_MAX_DURATION: f64 = 15.0;
+
+/// Part of the timeline starting at `start` seconds, as a sub-timeline for
+/// `build_filter_graph`. A partly played video is entered by moving its in
+/// point, and a looping animation by seeking its input, which is always the
+/// first input of the sliced graph; clips with speed, direction or loop
+/// modes can't be seeked in source time, so their rendered output is
+/// skipped into instead.
+/// Returns (items, cover, seek for the first input, output skip).
+fn slice_timeline(
+    media_items: &[MediaItem],
+    cover: &CoverConfig,
+    start: f64,
+) -> (Vec<MediaItem>, CoverConfig, Option<f64>, Option<f64>) {
+    let mut t = start.max(0.0);
+    let mut cover = cover.clone();
+
+    if cover.enabled && !cover.text.is_empty() {
+        if t < cover.duration {
+            cover.duration -= t;
+            return (media_items.to_vec(), cover, None, None);
+        }
+        t -= cover.duration;
+    }
+    cover.enabled = false;
+
+    for (i, item) in media_items.iter().enumerate() {
+        let is_last = i == media_items.len() - 1;
+        let item_duration = item.timeline_duration();
+        if t >= item_duration && !is_last {
+            t -= item_duration;
+            continue;
+        }
+
+        // Past the end of the timeline: stay on the final frames
+        let offset = t.min((item_duration - 0.05).max(0.0));
+        let mut rest = media_items[i..].to_vec();
+        if rest[0].media_type == "image" {
+            rest[0].duration = (rest[0].duration - offset).max(0.05);
+            rest[0].transform = rest[0].transform.as_ref().map(|t| t.shifted(offset));
+            return (rest, cover, None, None);
+        }
+        if rest[0].is_animated {
+            // A looping input: play what's left, from the same point in the loop
+            rest[0].duration = (rest[0].duration - offset).max(0.05);
+            let seek = rest[0]
+                .loop_duration
+                .filter(|d| *d > 0.0)
+                .map(|d| offset % d)
+                .filter(|ss| *ss > 0.0);
+            return (rest, cover, seek, None);
+        }
+        if offset <= 0.0 {
+            return (rest, cover, None, None);
+        }
+        if rest[0].has_playback_modes() {
+            return (rest, cover, None, Some(offset));
+        }
+        if let Some(seq) = rest[0].image_sequence.as_mut() {
+            // Frames are numbered without gaps, so start further along
+            // rather than seeking past the input's frame limit
+            let skip = ((offset * seq.framerate).floor() as u32).min(seq.frame_count.saturating_sub(1));
+            seq.start_number += skip;
+            seq.frame_count -= skip;
+            let skipped = skip as f64 / seq.framerate;
+            rest[0].duration = seq.frame_count as f64 / seq.framerate;
+            rest[0].transform = rest[0].transform.as_ref().map(|t| t.shifted(skipped));
+            return (rest, cover, None, None);
+        }
+        rest[0].trim_start = Some(rest[0].trim().unwrap_or(0.0) + offset);
+        rest[0].duration -= offset;
+        return (rest, cover, None, None);
+    }
+
+    (Vec::new(), cover, None, None)
+}
+
+/// Stable key for a timeline render, invalidated when any source changes
+fn timeline_key(
+    media_items: &[MediaItem],
+    cover: &CoverConfig,
+    config: &ExportConfig,
+    variant: &str,
+) -> String {
+    let sources: Vec<String> = media_items
+        .iter()
+        .map(|m| cache_key(&m.path, "source"))
+        .collect();
+    let input = format!(
+        "{}|{}|{}|{}|{}",
+        serde_json::to_string(media_items).unwrap_or_default(),
+        serde_json::to_string(cover).unwrap_or_default(),
+        serde_json::to_string(config).unwrap_or_default(),
+        sources.join(","),
+        variant
+    );
+    content_key(&input)
+}
+
+/// Args shared by frame and clip renders: the sliced composition's inputs
+/// and filter graph at `width`x`height`, with `extra_filters` appended to
+/// the graph
+fn composition_args(
+    media_items: &[MediaItem],
+    cover: &CoverConfig,
+    config: &ExportConfig,
+    width: u32,
+    height: u32,
+    start: f64,
+    extra_filters: &str,
+) -> Result<Vec<String>, String> {
+    let (items, cover, seek, skip) = slice_timeline(media_items, cover, start);
+    let has_cover = cover.enabled && !cover.text.is_empty();
+    if items.is_empty() && !has_cover {
+        return Err("Nothing to preview".to_string());
+    }
+
+    let (inputs, filter_complex) = build_filter_graph(&items, &cover, config, width, height);
+
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-v".to_string(), "error".to_string(),
+    ];
+    if let Some(ss) = seek {
+        args.extend(["-ss".to_string(), format!("{:.3}", ss)]);
+    }
+    args.extend(inputs);
+    args.extend([
+        "-filter_complex".to_string(),
+        format!("{};{}", filter_complex, extra_filters),
+    ]);
+    if let Some(skip) = skip {
+        // Output option: decode and drop the start of the first item
+        args.extend(["-ss".to_string(), format!("{:.3}", skip)]);
+    }
+    Ok(args)
+}
+
+fn run_render(app: &AppHandle, args: &[String], output: &Path) -> Result<(), String> {
+    let result = run_ffmpeg_command(app, args)?;
+    if !result.status.success() || !output.exists() {
+        let _ = std::fs::remove_file(output);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        let error_msg = stderr
+            .lines()
+            .rfind(|line| !line.trim().is_empty())
+            .unwrap_or("Unknown FFmpeg error");
+        return Err(format!("Preview render failed: {}", error_msg));
+    }
+    Ok(())
+}
+
+/// Render the exported frame at `time` seconds - cover, fit/pad and scaling
+/// included - as a cached qcthumb:// URL. `max_width` shrinks the result.
+#[command]
+pub async fn render_preview_frame(
+    app: AppHandle,
+    media_items: Vec<MediaItem>,
+    cover: CoverConfig,
+    config: ExportConfig,
+    time: f64,
+    max_width: Option<u32>,
+) -> Result<String, String> {
+    let mut media_items = media_items;
+    tokio::task::spawn_blocking(move || {
+        let frames_dir = cache_dir("frames")?;
+        let variant = format!("preview-frame-{:.3}-{}", time, max_width.unwrap_or(0));
+        let file_name = format!("{}.jpg", timeline_key(&media_items, &cover, &config, &variant));
+        let frame_path = frames_dir.join(&file_name);
+
+        if frame_path.exists() {
+            return Ok(cache_url("frames", &file_name));
+        }
+
+        prepare_export_sources(&app, &mut media_items, config.width, config.height)?;
+        let extra = match max_width {
+            Some(w) if w < config.width => format!("[outv]scale={}:-2[pv];[outa]anullsink", w),
+            _ => "[outv]null[pv];[outa]anullsink".to_string(),
+        };
+        let mut args = composition_args(
+            &media_items, &cover, &config, config.width, config.height, time, &extra,
+        )?;
+
+        let temp_path = temp_cache_path(&frame_path);
+        args.extend([
+            "-map".to_string(), "[pv]".to_string(),
+            "-frames:v".to_string(), "1".to_string(),
+            "-q:v".to_string(), "2".to_string(),
+            "-y".to_string(), temp_path.to_string_lossy().to_string(),
+        ]);
+
+        run_render(&app, &args, &temp_path)?;
+        commit_cache_file(&temp_path, &frame_path)?;
+        Ok(cache_url("frames", &file_name))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Render a low-resolution H.264 preview of `duration` seconds of the
+/// composition starting at `start`. Returns the cached file path.
+#[command]
+pub async fn render_preview_clip(
+    app: AppHandle,
+    media_items: Vec<MediaItem>,
+    cover: CoverConfig,
+    config: ExportConfig,
+    start: f64,
+    duration: f64,
+) -> Result<String, String> {
+    let duration = duration.clamp(0.1, PREVIEW_CLIP_MAX_DURATION);
+    let mut media_items = media_items;
+
+    tokio::task::spawn_blocking(move || {
+        let previews_dir = cache_dir("previews")?;
+        let variant = format!("preview-clip-{:.3}-{:.3}", start, duration);
+        let clip_path = previews_dir.join(format!("{}.mp4", timeline_key(&media_items, &cover, &config, &variant)));
+
+        if clip_path.exists() {
+            return Ok(clip_path.to_string_lossy().to_string());
+        }
+
+        // Same composition at a smaller size, keeping the aspect ratio
+        let scale = (PREVIEW_CLIP_MAX_DIMENSION as f64 / config.width.max(config.height) as f64).min(1.0);
+        let width = ((config.width as f64 * scale / 2.0).round() as u32 * 2).max(2);
+        let height = ((config.height as f64 * scale / 2.0).round() as u32 * 2).max(2);
+        prepare_export_sources(&app, &mut media_items, width, height)?;
+
+        let mut args = composition_args(
+            &media_items, &cover, &config, width, height, start, "[outv]null[pv];[outa]anull[pa]",
+        )?;
+
+        let temp_path = temp_cache_path(&clip_path);
+        args.extend([
+            "-map".to_string(), "[pv]".to_string(),
+            "-map".to_string(), "[pa]".to_string(),
+            "-t".to_string(), format!("{:.3}", duration),
+            "-c:v".to_string(), "libx264".to_string(),
+            "-preset".to_string(), "ultrafast".to_string(),
+            "-crf".to_string(), "28".to_string(),
+            "-pix_fmt".to_string(), "yuv420p".to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "96k".to_string(),
+            "-movflags".to_string(), "+faststart".to_string(),
+            "-y".to_string(), temp_path.to_string_lossy().to_string(),
+        ]);
+
+        run_render(&app, &args, &temp_path)?;
+        commit_cache_file(&temp_path, &clip_path)?;
+        Ok(clip_path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+use tauri::http::{header, Request, Response, StatusCode};
+
+use super::cache::{cache_root, SERVED_KINDS};
+
+/// Cache entries are content-addressed, so a URL never changes meaning
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+fn content_type_for(file_name: &str) -> &'static str {
+    match file_name.rsplit('.').next().unwrap_or("") {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "webp" => "image/webp",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+fn is_safe_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+}
+
+fn empty_response(status: StatusCode) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(status)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(Cow::Borrowed(&[][..]))
+        .unwrap_or_else(|_| Response::new(Cow::Borrowed(&[][..])))
+}
+
+/// Serve a cached thumbnail or frame for `qcthumb://localhost/<kind>/<file>`
+pub fn serve_cached_file(request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+    let path = request.uri().path().trim_start_matches('/');
+    let Some((kind, file_name)) = path.split_once('/') else {
+        return empty_response(StatusCode::NOT_FOUND);
+    };
+
+    // Only serve known cache kinds and plain file names (no traversal)
+    if !SERVED_KINDS.contains(&kind) || !is_safe_file_name(file_name) {
+        return empty_response(StatusCode::NOT_FOUND);
+    }
+
+    let etag = format!("\"{}\"", file_name);
+    let not_modified = request
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v == etag);
+
+    if not_modified {
+        return Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag)
+            .header(header::CACHE_CONTROL, CACHE_CONTROL)
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(Cow::Borrowed(&[][..]))
+            .unwrap_or_else(|_| empty_response(StatusCode::NOT_MODIFIED));
+    }
+
+    let data = match std::fs::read(cache_root().join(kind).join(file_name)) {
+        Ok(data) => data,
+        Err(_) => return empty_response(StatusCode::NOT_FOUND),
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type_for(file_name))
+        .header(header::CONTENT_LENGTH, data.len())
+        .header(header::CACHE_CONTROL, CACHE_CONTROL)
+        .header(header::ETAG, etag)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(Cow::Owned(data))
+        .unwrap_or_else(|_| empty_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
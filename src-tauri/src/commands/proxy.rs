@@ -0,0 +1,266 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use tauri::{command, AppHandle, Emitter, Manager};
+use tokio::sync::Semaphore;
+
+use super::cache::{cache_dir, cache_key, commit_cache_file, temp_cache_path};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+/// Proxy height - small enough to decode smoothly, big enough to judge framing
+const PROXY_HEIGHT: u32 = 540;
+/// Proxy encodes running at once; they are CPU heavy
+const PROXY_CONCURRENCY: usize = 2;
+/// Sources the webview can already play smoothly don't get a proxy
+const MAX_DIRECT_PIXELS: u32 = 1920 * 1080;
+
+/// Proxy state for one source file.
+/// `status` is "not_needed", "queued", "generating", "ready" or "failed".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyEntry {
+    pub path: String,
+    pub status: String,
+    pub proxy_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Tracks proxies for imported videos. Proxies are preview-only: exports
+/// always read `MediaItem.path`, never anything from here.
+pub struct ProxyManager {
+    entries: Mutex<HashMap<String, ProxyEntry>>,
+    slots: Arc<Semaphore>,
+}
+
+impl Default for ProxyManager {
+    fn default() -> Self {
+        ProxyManager {
+            entries: Mutex::new(HashMap::new()),
+            slots: Arc::new(Semaphore::new(PROXY_CONCURRENCY)),
+        }
+    }
+}
+
+impl ProxyManager {
+    fn get(&self, path: &str) -> Option<ProxyEntry> {
+        self.entries.lock().ok()?.get(path).cloned()
+    }
+
+    fn set(&self, app: &AppHandle, entry: ProxyEntry) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(entry.path.clone(), entry.clone());
+        }
+        let _ = app.emit("proxy-status", entry);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeProxyStream {
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeProxy {
+    streams: Option<Vec<ProbeProxyStream>>,
+}
+
+/// Large or non-H.264 sources (4K, HEVC, ProRes...) stutter or fail in the webview
+fn needs_proxy(ffprobe_path: &Path, path: &str) -> bool {
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=codec_name,width,height",
+            path,
+        ])
+        .output();
+
+    let Some(stream) = output
+        .ok()
+        .and_then(|o| serde_json::from_slice::<ProbeProxy>(&o.stdout).ok())
+        .and_then(|p| p.streams)
+        .and_then(|s| s.into_iter().next())
+    else {
+        return false;
+    };
+
+    let is_h264 = stream.codec_name.as_deref() == Some("h264");
+    let pixels = stream.width.unwrap_or(0).saturating_mul(stream.height.unwrap_or(0));
+    !is_h264 || pixels > MAX_DIRECT_PIXELS
+}
+
+fn proxy_file_name(path: &str) -> String {
+    format!("{}.mp4", cache_key(path, &format!("proxy-{}", PROXY_HEIGHT)))
+}
+
+/// Transcode a source into a small, widely playable H.264 proxy
+fn generate_proxy_sync(ffmpeg_path: &Path, path: &str, target: &Path) -> Result<(), String> {
+    let temp_path = temp_cache_path(target);
+
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-v", "error",
+            "-i", path,
+            "-map", "0:v:0",
+            "-map", "0:a:0?",
+            "-vf", &format!("scale=-2:'min({},ih)',format=yuv420p", PROXY_HEIGHT),
+            "-c:v", "libx264",
+            "-preset", "veryfast",
+            "-crf", "28",
+            // Short GOP keeps scrubbing responsive
+            "-g", "15",
+            "-c:a", "aac",
+            "-b:a", "96k",
+            "-movflags", "+faststart",
+            "-y",
+            &temp_path.to_string_lossy(),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() || !temp_path.exists() {
+        let _ = std::fs::remove_file(&temp_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error_msg = stderr
+            .lines()
+            .rfind(|line| !line.trim().is_empty())
+            .unwrap_or("Unknown FFmpeg error");
+        return Err(error_msg.to_string());
+    }
+
+    commit_cache_file(&temp_path, target)
+}
+
+/// Queue proxy generation for imported videos. Runs in the background and
+/// reports changes through "proxy-status" events.
+#[command]
+pub async fn request_proxies(app: AppHandle, paths: Vec<String>) -> Result<Vec<ProxyEntry>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let proxies_dir = cache_dir("proxies")?;
+    let manager = app.state::<ProxyManager>();
+
+    let mut entries = Vec::with_capacity(paths.len());
+    for path in paths {
+        // Already queued, running or done in this session
+        if let Some(entry) = manager.get(&path).filter(|e| e.status != "failed") {
+            entries.push(entry);
+            continue;
+        }
+
+        let target = proxies_dir.join(proxy_file_name(&path));
+        let mut entry = ProxyEntry {
+            path: path.clone(),
+            status: "queued".to_string(),
+            proxy_path: None,
+            error: None,
+        };
+
+        if target.exists() {
+            entry.status = "ready".to_string();
+            entry.proxy_path = Some(target.to_string_lossy().to_string());
+        } else {
+            let probe_path = path.clone();
+            let ffprobe = ffprobe_path.clone();
+            let needed = tokio::task::spawn_blocking(move || needs_proxy(&ffprobe, &probe_path))
+                .await
+                .unwrap_or(false);
+            if !needed {
+                entry.status = "not_needed".to_string();
+            }
+        }
+
+        manager.set(&app, entry.clone());
+
+        if entry.status == "queued" {
+            let app = app.clone();
+            let ffmpeg = ffmpeg_path.clone();
+            let slots = manager.slots.clone();
+            tokio::spawn(async move {
+                let Ok(_permit) = slots.acquire_owned().await else { return };
+                let manager = app.state::<ProxyManager>();
+                manager.set(&app, ProxyEntry {
+                    path: path.clone(),
+                    status: "generating".to_string(),
+                    proxy_path: None,
+                    error: None,
+                });
+
+                let source = path.clone();
+                let proxy_target = target.clone();
+                let result = tokio::task::spawn_blocking(move || generate_proxy_sync(&ffmpeg, &source, &proxy_target))
+                    .await
+                    .map_err(|e| format!("Task failed: {}", e))
+                    .and_then(|r| r);
+
+                let done = match result {
+                    Ok(()) => ProxyEntry {
+                        path,
+                        status: "ready".to_string(),
+                        proxy_path: Some(target.to_string_lossy().to_string()),
+                        error: None,
+                    },
+                    Err(e) => {
+                        log::warn!("Proxy generation failed for {}: {}", path, e);
+                        ProxyEntry {
+                            path,
+                            status: "failed".to_string(),
+                            proxy_path: None,
+                            error: Some(e),
+                        }
+                    }
+                };
+                manager.set(&app, done);
+            });
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Current proxy state for the given files
+#[command]
+pub async fn get_proxy_status(app: AppHandle, paths: Vec<String>) -> Result<Vec<ProxyEntry>, String> {
+    let manager = app.state::<ProxyManager>();
+    Ok(paths.iter().filter_map(|p| manager.get(p)).collect())
+}
+
+/// Delete proxies for files that are no longer on the timeline.
+/// Returns the number of proxy files removed.
+#[command]
+pub async fn cleanup_proxies(app: AppHandle, keep_paths: Vec<String>) -> Result<u32, String> {
+    let manager = app.state::<ProxyManager>();
+    if let Ok(mut entries) = manager.entries.lock() {
+        entries.retain(|path, _| keep_paths.contains(path));
+    }
+
+    let proxies_dir = cache_dir("proxies")?;
+    tokio::task::spawn_blocking(move || {
+        let keep: HashSet<String> = keep_paths.iter().map(|p| proxy_file_name(p)).collect();
+        let mut removed = 0;
+
+        let entries = std::fs::read_dir(&proxies_dir)
+            .map_err(|e| format!("Failed to read proxy cache: {}", e))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Leave in-flight temp files alone; their encode is still running
+            if name.contains(".part.") || keep.contains(&name) {
+                continue;
+            }
+            if std::fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
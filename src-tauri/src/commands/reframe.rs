@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, read_cached_json, write_cached_json};
+use super::sidecar::get_ffmpeg_path;
+use super::transform::{CropKeyframe, CropRect};
+
+/// Frames are analysed small and grey, a few per second
+const ANALYSIS_WIDTH: u32 = 160;
+const ANALYSIS_FPS: f64 = 4.0;
+
+/// Mean luma change (0-255) between samples that marks a cut
+const SCENE_CUT_THRESHOLD: f64 = 30.0;
+/// Moving pixels count this much more than busy, detailed ones
+const MOTION_WEIGHT: f64 = 3.0;
+/// Mild pull towards the middle so flat frames don't wander
+const CENTRE_BIAS: f64 = 0.15;
+/// Time constant of the camera-like smoothing within a shot
+const SMOOTHING_SECS: f64 = 0.8;
+/// Path points closer than this (share of the frame) to a straight move
+/// are dropped
+const PATH_TOLERANCE: f64 = 0.01;
+
+/// A crop path that keeps the subject in a frame of another aspect ratio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReframePath {
+    pub crop_keyframes: Vec<CropKeyframe>, // source pixels, as displayed
+    pub shot_count: u32,
+}
+
+/// Where the crop window starts in one analysed frame, as a share of the
+/// cropped dimension
+struct Sample {
+    time: f64,
+    position: f64,
+    is_cut: bool,
+}
+
+/// Interest along one axis of a frame: local contrast plus weighted motion
+/// against the previous frame, summed across the other axis
+fn interest_profile(frame: &[u8], previous: Option<&[u8]>, width: usize, height: usize, horizontal: bool) -> Vec<f64> {
+    let mut profile = vec![0.0; if horizontal { width } else { height }];
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            let p = frame[i] as f64;
+            let right = if x + 1 < width { frame[i + 1] as f64 } else { p };
+            let below = if y + 1 < height { frame[i + width] as f64 } else { p };
+            let mut score = (p - right).abs() + (p - below).abs();
+            if let Some(prev) = previous {
+                score += MOTION_WEIGHT * (p - prev[i] as f64).abs();
+            }
+            profile[if horizontal { x } else { y }] += score;
+        }
+    }
+    profile
+}
+
+/// Start of the `window`-long stretch of `profile` with the most interest,
+/// as a share of the profile length
+fn best_window(profile: &[f64], window: usize) -> f64 {
+    let len = profile.len();
+    if window >= len {
+        return 0.0;
+    }
+    let mid = (len - window) as f64 / 2.0;
+    let mut sum: f64 = profile[..window].iter().sum();
+    let mut best = (f64::MIN, 0);
+    for start in 0..=len - window {
+        if start > 0 {
+            sum += profile[start + window - 1] - profile[start - 1];
+        }
+        let bias = 1.0 - CENTRE_BIAS * (start as f64 - mid).abs() / mid.max(1.0);
+        if sum * bias > best.0 {
+            best = (sum * bias, start);
+        }
+    }
+    best.1 as f64 / len as f64
+}
+
+/// Forward-backward exponential smoothing: steady like a camera operator,
+/// without lagging behind the subject
+fn smooth(values: &mut [f64]) {
+    let alpha = 1.0 - (-1.0 / (ANALYSIS_FPS * SMOOTHING_SECS)).exp();
+    for i in 1..values.len() {
+        values[i] = values[i - 1] + alpha * (values[i] - values[i - 1]);
+    }
+    for i in (0..values.len().saturating_sub(1)).rev() {
+        values[i] = values[i + 1] + alpha * (values[i] - values[i + 1]);
+    }
+}
+
+/// Ramer-Douglas-Peucker on (time, position): keep only the points the
+/// straight-line moves between them can't stand in for
+fn simplify(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let span = (last.0 - first.0).max(f64::EPSILON);
+    let (index, distance) = points[1..points.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let on_line = first.1 + (last.1 - first.1) * (p.0 - first.0) / span;
+            (i + 1, (p.1 - on_line).abs())
+        })
+        .fold((0, 0.0), |best, d| if d.1 > best.1 { d } else { best });
+
+    if distance <= PATH_TOLERANCE {
+        return vec![first, last];
+    }
+    let mut left = simplify(&points[..=index]);
+    left.pop();
+    left.extend(simplify(&points[index..]));
+    left
+}
+
+/// Decode small grey frames and pick the crop window's position in each
+fn analyze_samples(
+    path: &str,
+    ffmpeg_path: &Path,
+    width: usize,
+    height: usize,
+    horizontal: bool,
+    window: usize,
+) -> Result<Vec<Sample>, String> {
+    let mut child = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-v", "error",
+            "-i", path,
+            "-map", "0:v:0",
+            "-an",
+            "-vf", &format!("fps={},scale={}:{},format=gray", ANALYSIS_FPS, width, height),
+            "-f", "rawvideo",
+            "-",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to read ffmpeg output".to_string())?;
+
+    let mut samples = Vec::new();
+    let mut frame = vec![0u8; width * height];
+    let mut previous: Option<Vec<u8>> = None;
+    while stdout.read_exact(&mut frame).is_ok() {
+        let is_cut = previous.as_ref().is_some_and(|prev| {
+            let diff: u64 = frame.iter().zip(prev).map(|(a, b)| a.abs_diff(*b) as u64).sum();
+            diff as f64 / frame.len() as f64 > SCENE_CUT_THRESHOLD
+        });
+        // Motion across a cut is just the cut
+        let motion_ref = if is_cut { None } else { previous.as_deref() };
+        let profile = interest_profile(&frame, motion_ref, width, height, horizontal);
+        samples.push(Sample {
+            time: samples.len() as f64 / ANALYSIS_FPS,
+            position: best_window(&profile, window),
+            is_cut,
+        });
+        previous = Some(frame.clone());
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !status.success() && samples.is_empty() {
+        return Err("Failed to decode video".to_string());
+    }
+    Ok(samples)
+}
+
+/// Smoothed crop path through the samples: a steady move within each shot
+/// and a hard jump at each cut
+fn crop_path(samples: &[Sample], max_position: f64) -> (Vec<(f64, f64)>, u32) {
+    let mut path: Vec<(f64, f64)> = Vec::new();
+    let mut shot_count = 0;
+    let mut start = 0;
+    while start < samples.len() {
+        let end = samples[start + 1..]
+            .iter()
+            .position(|s| s.is_cut)
+            .map_or(samples.len(), |i| start + 1 + i);
+
+        let mut positions: Vec<f64> = samples[start..end].iter().map(|s| s.position).collect();
+        smooth(&mut positions);
+        let points: Vec<(f64, f64)> = samples[start..end]
+            .iter()
+            .zip(positions)
+            .map(|(s, p)| (s.time, p.clamp(0.0, max_position)))
+            .collect();
+        let mut shot = simplify(&points);
+
+        // The cut falls somewhere between the two samples; jump halfway
+        if let (Some(&(prev_time, prev_pos)), Some(first)) = (path.last(), shot.first_mut()) {
+            let cut_time = (prev_time + first.0) / 2.0;
+            path.push((cut_time, prev_pos));
+            first.0 = first.0.max(cut_time + 0.001);
+            shot.insert(0, (cut_time + 0.001, shot[0].1));
+        }
+        path.extend(shot);
+        shot_count += 1;
+        start = end;
+    }
+    (path, shot_count)
+}
+
+fn analyze_reframe_sync(
+    path: &str,
+    ffmpeg_path: &Path,
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+) -> Result<ReframePath, String> {
+    if width == 0 || height == 0 || target_width == 0 || target_height == 0 {
+        return Err("Unknown frame size".to_string());
+    }
+
+    // Largest window of the target shape inside the source
+    let target_aspect = target_width as f64 / target_height as f64;
+    let source_aspect = width as f64 / height as f64;
+    let horizontal = source_aspect > target_aspect;
+    let (crop_width, crop_height) = if horizontal {
+        (height as f64 * target_aspect, height as f64)
+    } else {
+        (width as f64, width as f64 / target_aspect)
+    };
+    if (source_aspect / target_aspect - 1.0).abs() < 0.01 {
+        return Ok(ReframePath { crop_keyframes: Vec::new(), shot_count: 0 });
+    }
+
+    let reframe_dir = cache_dir("reframe")?;
+    let variant = format!("reframe-{}x{}-{}x{}", width, height, target_width, target_height);
+    let cache_path = reframe_dir.join(format!("{}.json", cache_key(path, &variant)));
+    if let Some(cached) = read_cached_json::<ReframePath>(&cache_path) {
+        return Ok(cached);
+    }
+
+    let analysis_width = ANALYSIS_WIDTH as usize;
+    let analysis_height = (((ANALYSIS_WIDTH as f64 / source_aspect) / 2.0).round() as usize * 2).max(2);
+    let (axis_len, crop_share) = if horizontal {
+        (analysis_width, crop_width / width as f64)
+    } else {
+        (analysis_height, crop_height / height as f64)
+    };
+    let window = ((axis_len as f64 * crop_share).round() as usize).clamp(1, axis_len);
+
+    let samples = analyze_samples(path, ffmpeg_path, analysis_width, analysis_height, horizontal, window)?;
+    let (points, shot_count) = crop_path(&samples, 1.0 - crop_share);
+
+    let crop_keyframes = points
+        .into_iter()
+        .map(|(time, position)| CropKeyframe {
+            time,
+            rect: if horizontal {
+                CropRect { x: position * width as f64, y: 0.0, width: crop_width, height: crop_height }
+            } else {
+                CropRect { x: 0.0, y: position * height as f64, width: crop_width, height: crop_height }
+            },
+        })
+        .collect();
+    let reframe = ReframePath { crop_keyframes, shot_count };
+
+    write_cached_json(&cache_path, &reframe);
+    Ok(reframe)
+}
+
+/// Follow the subject of a `width`x`height` video (as displayed) with a
+/// crop window of the target aspect ratio. Motion, detail and scene cuts
+/// are measured locally; the result feeds `ClipTransform.crop_keyframes`.
+#[command]
+pub async fn analyze_reframe(
+    app: AppHandle,
+    path: String,
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+) -> Result<ReframePath, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    tokio::task::spawn_blocking(move || {
+        analyze_reframe_sync(&path, &ffmpeg_path, width, height, target_width, target_height)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
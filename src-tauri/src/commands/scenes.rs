@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, read_cached_json, write_cached_json};
+use super::frames::{frame_url_at, probe_video_timing};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+const DEFAULT_SENSITIVITY: f64 = 0.5;
+/// Scene scores below this are never cuts, at any sensitivity - also the
+/// floor for what gets cached
+const MIN_SCENE_SCORE: f64 = 0.1;
+/// Cuts closer together than this are flashes or flicker, not shots
+const MIN_SCENE_SECS: f64 = 1.0;
+const SCENE_THUMB_WIDTH: u32 = 160;
+
+/// A detected cut: the first frame of a new shot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneBoundary {
+    pub time: f64,  // seconds into the source
+    pub score: f64, // ffmpeg scene score, 0-1; higher is a harder cut
+    pub thumbnail: Option<String>, // qcthumb:// URL of the new shot's first frame
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneDetection {
+    pub duration: f64,
+    pub boundaries: Vec<SceneBoundary>,
+}
+
+/// One piece of a split item: `duration` seconds from `trim_start`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneClip {
+    pub trim_start: f64,
+    pub duration: f64,
+    pub thumbnail: Option<String>,
+}
+
+/// Score a frame must beat to count as a cut: 0.5 at sensitivity 0, down
+/// to 0.1 at sensitivity 1
+fn scene_threshold(sensitivity: f64) -> f64 {
+    0.5 - 0.4 * sensitivity.clamp(0.0, 1.0)
+}
+
+/// (time, score) pairs from the metadata filter's printout:
+/// "frame:12 pts:... pts_time:4.2" followed by "lavfi.scene_score=0.61"
+fn parse_scene_scores(stdout: &str) -> Vec<(f64, f64)> {
+    let mut scores = Vec::new();
+    let mut time = None;
+    for line in stdout.lines() {
+        if let Some(pos) = line.find("pts_time:") {
+            time = line[pos + 9..].split_whitespace().next().and_then(|t| t.parse::<f64>().ok());
+        } else if let Some(value) = line.trim().strip_prefix("lavfi.scene_score=") {
+            if let (Some(t), Ok(score)) = (time.take(), value.parse::<f64>()) {
+                scores.push((t, score));
+            }
+        }
+    }
+    scores
+}
+
+/// Every frame scoring above `MIN_SCENE_SCORE`, cached so changing the
+/// sensitivity doesn't decode the video again
+fn scene_scores(ffmpeg_path: &Path, path: &str) -> Result<Vec<(f64, f64)>, String> {
+    let cache_path = cache_dir("scenes")?.join(format!("{}.json", cache_key(path, "scene-scores")));
+    if let Some(cached) = read_cached_json::<Vec<(f64, f64)>>(&cache_path) {
+        return Ok(cached);
+    }
+
+    // Scores barely change at low resolution and decode much faster
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-v", "error",
+            "-i", path,
+            "-map", "0:v:0",
+            "-an",
+            "-vf", &format!(
+                "scale=320:-2,select='gt(scene,{})',metadata=print:file=-",
+                MIN_SCENE_SCORE
+            ),
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err("Failed to analyse scenes".to_string());
+    }
+
+    let scores = parse_scene_scores(&String::from_utf8_lossy(&output.stdout));
+    write_cached_json(&cache_path, &scores);
+    Ok(scores)
+}
+
+/// Cuts above the threshold, at least `MIN_SCENE_SECS` apart and from
+/// either end. Of two cuts too close together the harder one wins.
+fn pick_boundaries(scores: &[(f64, f64)], threshold: f64, duration: f64) -> Vec<(f64, f64)> {
+    let mut picked: Vec<(f64, f64)> = Vec::new();
+    for &(time, score) in scores.iter().filter(|(_, s)| *s > threshold) {
+        if time < MIN_SCENE_SECS || (duration > 0.0 && time > duration - MIN_SCENE_SECS) {
+            continue;
+        }
+        match picked.last_mut() {
+            Some(last) if time - last.0 < MIN_SCENE_SECS => {
+                if score > last.1 {
+                    *last = (time, score);
+                }
+            }
+            _ => picked.push((time, score)),
+        }
+    }
+    picked
+}
+
+/// Find shot boundaries in a video. `sensitivity` runs from 0 (hard cuts
+/// only) to 1 (also soft ones); 0.5 by default.
+#[command]
+pub async fn detect_scenes(app: AppHandle, path: String, sensitivity: Option<f64>) -> Result<SceneDetection, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let threshold = scene_threshold(sensitivity.unwrap_or(DEFAULT_SENSITIVITY));
+
+    tokio::task::spawn_blocking(move || {
+        let duration = probe_video_timing(&ffprobe_path, &path).0.unwrap_or(0.0);
+        let scores = scene_scores(&ffmpeg_path, &path)?;
+        let boundaries = pick_boundaries(&scores, threshold, duration)
+            .into_iter()
+            .map(|(time, score)| SceneBoundary {
+                time,
+                score,
+                thumbnail: frame_url_at(&ffmpeg_path, &path, time, Some(SCENE_THUMB_WIDTH)).ok(),
+            })
+            .collect();
+        Ok(SceneDetection { duration, boundaries })
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Split the `duration` seconds of `path` starting at `trim_start` into
+/// trimmed clips at `cut_times` (seconds into the source). Nothing is
+/// re-encoded - each clip is just a new in point and length for export.
+#[command]
+pub async fn split_at_scenes(
+    app: AppHandle,
+    path: String,
+    trim_start: Option<f64>,
+    duration: f64,
+    cut_times: Vec<f64>,
+) -> Result<Vec<SceneClip>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let start = trim_start.unwrap_or(0.0).max(0.0);
+    let end = start + duration;
+
+    let mut cuts: Vec<f64> = cut_times
+        .into_iter()
+        .filter(|t| *t > start + 0.1 && *t < end - 0.1)
+        .collect();
+    cuts.sort_by(f64::total_cmp);
+    cuts.dedup_by(|a, b| (*a - *b).abs() < 0.1);
+
+    let mut edges = vec![start];
+    edges.extend(cuts);
+    edges.push(end);
+
+    tokio::task::spawn_blocking(move || {
+        Ok(edges
+            .windows(2)
+            .map(|pair| SceneClip {
+                trim_start: pair[0],
+                duration: pair[1] - pair[0],
+                thumbnail: frame_url_at(&ffmpeg_path, &path, pair[0], Some(SCENE_THUMB_WIDTH)).ok(),
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // metadata=print:file=- output for three selected frames
+    const METADATA_PRINT: &str = "\
+frame:0    pts:126126  pts_time:4.2042
+lavfi.scene_score=0.612345
+frame:1    pts:243243  pts_time:8.1081
+lavfi.scene_score=0.134002
+frame:2    pts:378378  pts_time:12.6126
+lavfi.scene_score=0.980000
+";
+
+    #[test]
+    fn parses_scene_scores() {
+        assert_eq!(
+            parse_scene_scores(METADATA_PRINT),
+            vec![(4.2042, 0.612345), (8.1081, 0.134002), (12.6126, 0.98)]
+        );
+    }
+
+    #[test]
+    fn skips_scores_without_a_time() {
+        let stdout = "lavfi.scene_score=0.5\nframe:3    pts:1001  pts_time:oops\nlavfi.scene_score=0.7\n";
+        assert!(parse_scene_scores(stdout).is_empty());
+        assert!(parse_scene_scores("").is_empty());
+    }
+
+    #[test]
+    fn picks_cuts_above_the_threshold() {
+        let scores = parse_scene_scores(METADATA_PRINT);
+        assert_eq!(pick_boundaries(&scores, 0.3, 20.0), vec![(4.2042, 0.612345), (12.6126, 0.98)]);
+        assert_eq!(pick_boundaries(&scores, 0.1, 20.0).len(), 3);
+        assert!(pick_boundaries(&scores, 0.99, 20.0).is_empty());
+    }
+
+    #[test]
+    fn keeps_the_harder_of_two_close_cuts() {
+        let scores = [(5.0, 0.4), (5.5, 0.8), (6.2, 0.5), (8.0, 0.6)];
+        // 6.2 is within a second of the 5.5 that replaced 5.0
+        assert_eq!(pick_boundaries(&scores, 0.3, 20.0), vec![(5.5, 0.8), (8.0, 0.6)]);
+        assert_eq!(pick_boundaries(&[(5.0, 0.9), (5.5, 0.4)], 0.3, 20.0), vec![(5.0, 0.9)]);
+    }
+
+    #[test]
+    fn drops_cuts_near_either_end() {
+        let scores = [(0.0, 0.9), (0.5, 0.9), (1.0, 0.9), (9.0, 0.9), (9.5, 0.9)];
+        assert_eq!(pick_boundaries(&scores, 0.3, 10.0), vec![(1.0, 0.9), (9.0, 0.9)]);
+        // Unknown duration only guards the start
+        assert_eq!(pick_boundaries(&[(0.5, 0.9), (9.5, 0.9)], 0.3, 0.0), vec![(9.5, 0.9)]);
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{command, AppHandle};
+
+use super::metadata::{generate_thumbnail_sync, get_metadata_fast, MediaMetadata};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+/// Fewer numbered files than this are just photos, not a timelapse
+const MIN_SEQUENCE_FRAMES: usize = 10;
+const DEFAULT_SEQUENCE_FRAMERATE: f64 = 24.0;
+
+/// Formats ffmpeg's image2 demuxer reads frame by frame
+const SEQUENCE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "tif", "tiff", "bmp", "webp"];
+
+/// A run of numbered stills found among imported files, e.g.
+/// DSC_0001.JPG ... DSC_0600.JPG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageSequence {
+    pub pattern: String, // image2 pattern, e.g. "/shoot/DSC_%04d.JPG"
+    pub name: String,    // e.g. "DSC_0001-0600.JPG"
+    pub first_path: String,
+    pub start_number: u32,
+    pub frame_count: u32,
+    pub paths: Vec<String>, // member files, in order
+}
+
+/// Result of grouping imported files into sequences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceScan {
+    pub sequences: Vec<ImageSequence>,
+    pub other_paths: Vec<String>, // everything not part of a sequence
+}
+
+/// How export reads a sequence clip: `MediaItem.path` is the pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceInput {
+    pub start_number: u32,
+    pub frame_count: u32,
+    pub framerate: f64,
+}
+
+/// Splits "DSC_0042.JPG" into ("DSC_", "0042", ".JPG") around the last run
+/// of digits in the stem
+fn split_frame_number(file_name: &str) -> Option<(&str, &str, &str)> {
+    let ext_start = file_name.rfind('.')?;
+    let ext = file_name[ext_start + 1..].to_lowercase();
+    if !SEQUENCE_EXTENSIONS.contains(&ext.as_str()) {
+        return None;
+    }
+
+    let stem = &file_name[..ext_start];
+    let end = stem.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = stem[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    Some((&file_name[..start], &file_name[start..end], &file_name[end..]))
+}
+
+/// Files of the given paths, with directories expanded one level
+fn expand_paths(paths: &[String]) -> Vec<String> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !Path::new(path).is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(path) else { continue };
+        let mut dir_files: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().is_file())
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .map(|e| e.path().to_string_lossy().to_string())
+            .collect();
+        dir_files.sort();
+        files.extend(dir_files);
+    }
+    files
+}
+
+/// Files that can belong to one sequence: directory, prefix, digit count
+/// and suffix all match
+type SequenceKey = (String, String, usize, String);
+
+/// Group numbered stills into sequences. The image2 demuxer stops at the
+/// first missing number, so a gap starts a new sequence.
+fn find_sequences(paths: &[String]) -> SequenceScan {
+    let mut groups: BTreeMap<SequenceKey, Vec<(u32, String)>> = BTreeMap::new();
+    let mut other_paths = Vec::new();
+
+    for path in expand_paths(paths) {
+        let parsed = Path::new(&path).file_name().and_then(|n| n.to_str()).and_then(|name| {
+            let (prefix, digits, suffix) = split_frame_number(name)?;
+            let number = digits.parse::<u32>().ok()?;
+            let dir = Path::new(&path).parent()?.to_string_lossy().to_string();
+            Some(((dir, prefix.to_string(), digits.len(), suffix.to_string()), number))
+        });
+        match parsed {
+            Some((key, number)) => groups.entry(key).or_default().push((number, path)),
+            None => other_paths.push(path),
+        }
+    }
+
+    let mut sequences = Vec::new();
+    for ((dir, prefix, width, suffix), mut frames) in groups {
+        frames.sort();
+        frames.dedup_by_key(|(n, _)| *n);
+
+        let mut runs: Vec<Vec<(u32, String)>> = Vec::new();
+        for frame in frames {
+            match runs.last_mut() {
+                Some(run) if run.last().is_some_and(|(n, _)| *n + 1 == frame.0) => run.push(frame),
+                _ => runs.push(vec![frame]),
+            }
+        }
+
+        for run in runs {
+            if run.len() < MIN_SEQUENCE_FRAMES {
+                other_paths.extend(run.into_iter().map(|(_, p)| p));
+                continue;
+            }
+            let first = run.first().map_or(0, |(n, _)| *n);
+            let last = run.last().map_or(0, |(n, _)| *n);
+            // '%' is special in image2 patterns
+            let pattern = Path::new(&dir)
+                .join(format!("{}%0{}d{}", prefix.replace('%', "%%"), width, suffix.replace('%', "%%")))
+                .to_string_lossy()
+                .to_string();
+            sequences.push(ImageSequence {
+                pattern,
+                name: format!("{}{:0w$}-{:0w$}{}", prefix, first, last, suffix, w = width),
+                first_path: run[0].1.clone(),
+                start_number: first,
+                frame_count: run.len() as u32,
+                paths: run.into_iter().map(|(_, p)| p).collect(),
+            });
+        }
+    }
+
+    SequenceScan { sequences, other_paths }
+}
+
+/// Find numbered image sequences among imported files and folders
+#[command]
+pub async fn find_image_sequences(paths: Vec<String>) -> Result<SequenceScan, String> {
+    tokio::task::spawn_blocking(move || find_sequences(&paths))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))
+}
+
+/// Metadata for a sequence played as one clip at `framerate`. Size, codec
+/// and capture time come from the first frame, which is also the thumbnail.
+#[command]
+pub async fn get_image_sequence_metadata(
+    app: AppHandle,
+    sequence: ImageSequence,
+    framerate: Option<f64>,
+) -> Result<MediaMetadata, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let framerate = framerate.filter(|f| *f > 0.0).unwrap_or(DEFAULT_SEQUENCE_FRAMERATE);
+
+    tokio::task::spawn_blocking(move || {
+        let mut metadata = get_metadata_fast(sequence.first_path.clone(), &ffprobe_path, &ffmpeg_path)?;
+        metadata.thumbnail =
+            generate_thumbnail_sync(&sequence.first_path, "image", &ffmpeg_path, &ffprobe_path, None);
+
+        metadata.path = sequence.pattern;
+        metadata.name = sequence.name;
+        metadata.media_type = "video".to_string();
+        metadata.duration = sequence.frame_count as f64 / framerate;
+        metadata.framerate = Some(framerate);
+        metadata.avg_framerate = Some(framerate);
+        metadata.frame_count = Some(sequence.frame_count);
+        metadata.bitrate = None;
+        metadata.image_sequence = Some(SequenceInput {
+            start_number: sequence.start_number,
+            frame_count: sequence.frame_count,
+            framerate,
+        });
+        Ok(metadata)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, read_cached_json, write_cached_json};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+use super::waveform::probe_audio;
+
+/// Audio quieter than this is silence
+const DEFAULT_NOISE_DB: f64 = -35.0;
+/// Shorter quiet stretches are just gaps between words
+const DEFAULT_MIN_SILENCE: f64 = 0.5;
+/// Silence kept either side of speech so cuts don't clip breaths and
+/// word endings
+const SILENCE_PADDING: f64 = 0.15;
+/// Bits of sound shorter than this between two silences are dropped
+const MIN_SPEECH_CLIP: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SilentRange {
+    pub start: f64, // seconds into the source
+    pub end: f64,
+}
+
+/// In and out points that cut a silent lead-in and tail
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TrimSuggestion {
+    pub trim_start: f64,
+    pub duration: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SilenceDetection {
+    pub has_audio: bool,
+    pub duration: f64,
+    pub silences: Vec<SilentRange>,
+    pub suggested_trim: Option<TrimSuggestion>, // None when there's nothing to trim
+}
+
+/// One stretch of sound kept when silences are removed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpeechClip {
+    pub trim_start: f64,
+    pub duration: f64,
+}
+
+/// Ranges from silencedetect's log: "silence_start: 1.2" and later
+/// "silence_end: 4.5 | silence_duration: 3.3". Silence running to the end
+/// of the file has no end line.
+fn parse_silences(stderr: &str, duration: f64) -> Vec<SilentRange> {
+    let value_after = |line: &str, key: &str| {
+        let pos = line.find(key)? + key.len();
+        line[pos..].split_whitespace().next()?.parse::<f64>().ok()
+    };
+
+    let mut silences = Vec::new();
+    let mut start = None;
+    for line in stderr.lines() {
+        if let Some(s) = value_after(line, "silence_start:") {
+            start = Some(s.max(0.0));
+        } else if let Some(end) = value_after(line, "silence_end:") {
+            if let Some(s) = start.take() {
+                silences.push(SilentRange { start: s, end });
+            }
+        }
+    }
+    if let Some(s) = start {
+        silences.push(SilentRange { start: s, end: duration.max(s) });
+    }
+    silences
+}
+
+fn detect_silences_sync(
+    path: &str,
+    ffmpeg_path: &Path,
+    ffprobe_path: &Path,
+    noise_db: f64,
+    min_silence: f64,
+) -> Result<SilenceDetection, String> {
+    let (has_audio, duration) = probe_audio(ffprobe_path, path)?;
+    let duration = duration.unwrap_or(0.0);
+    if !has_audio {
+        return Ok(SilenceDetection { has_audio, duration, silences: Vec::new(), suggested_trim: None });
+    }
+
+    let cache_path = cache_dir("silences")?.join(format!(
+        "{}.json",
+        cache_key(path, &format!("silences-{:.1}-{:.2}", noise_db, min_silence))
+    ));
+    if let Some(cached) = read_cached_json::<SilenceDetection>(&cache_path) {
+        return Ok(cached);
+    }
+
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-i", path,
+            "-map", "0:a:0",
+            "-vn",
+            "-af", &format!("silencedetect=noise={}dB:d={}", noise_db, min_silence),
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err("Failed to analyse audio".to_string());
+    }
+
+    let silences = parse_silences(&String::from_utf8_lossy(&output.stderr), duration);
+    let detection = SilenceDetection {
+        has_audio,
+        duration,
+        suggested_trim: suggest_trim(&silences, duration),
+        silences,
+    };
+    write_cached_json(&cache_path, &detection);
+    Ok(detection)
+}
+
+/// Skip silence at the very start and end, keeping a little padding
+fn suggest_trim(silences: &[SilentRange], duration: f64) -> Option<TrimSuggestion> {
+    let lead_in = silences
+        .first()
+        .filter(|s| s.start <= SILENCE_PADDING)
+        .map_or(0.0, |s| (s.end - SILENCE_PADDING).max(0.0));
+    let out = silences
+        .last()
+        .filter(|s| s.end >= duration - SILENCE_PADDING)
+        .map_or(duration, |s| (s.start + SILENCE_PADDING).min(duration));
+
+    if out - lead_in < MIN_SPEECH_CLIP || (lead_in <= 0.0 && out >= duration) {
+        return None;
+    }
+    Some(TrimSuggestion { trim_start: lead_in, duration: out - lead_in })
+}
+
+/// Sub-clips of `start`..`end` with every silence longer than
+/// `longer_than` cut out
+fn speech_clips(silences: &[SilentRange], start: f64, end: f64, longer_than: f64) -> Vec<SpeechClip> {
+    let mut clips = Vec::new();
+    let mut clip_start = start;
+    for silence in silences.iter().filter(|s| s.end - s.start > longer_than) {
+        let cut_start = (silence.start + SILENCE_PADDING).max(start);
+        let cut_end = (silence.end - SILENCE_PADDING).min(end);
+        if cut_end <= cut_start || cut_end <= clip_start {
+            continue;
+        }
+        if cut_start - clip_start >= MIN_SPEECH_CLIP {
+            clips.push(SpeechClip { trim_start: clip_start, duration: cut_start - clip_start });
+        }
+        clip_start = cut_end;
+    }
+    if end - clip_start >= MIN_SPEECH_CLIP {
+        clips.push(SpeechClip { trim_start: clip_start, duration: end - clip_start });
+    }
+    clips
+}
+
+/// Find silent stretches in a video's audio: quieter than `noise_db`
+/// (default -35 dB) for at least `min_duration` seconds (default 0.5).
+/// Also suggests in and out points that skip silence at either end.
+#[command]
+pub async fn detect_silences(
+    app: AppHandle,
+    path: String,
+    noise_db: Option<f64>,
+    min_duration: Option<f64>,
+) -> Result<SilenceDetection, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let noise_db = noise_db.unwrap_or(DEFAULT_NOISE_DB).min(0.0);
+    let min_duration = min_duration.unwrap_or(DEFAULT_MIN_SILENCE).max(0.05);
+
+    tokio::task::spawn_blocking(move || {
+        detect_silences_sync(&path, &ffmpeg_path, &ffprobe_path, noise_db, min_duration)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Cut every silence longer than `longer_than` seconds out of the
+/// `duration` seconds of `path` from `trim_start`. Returns the sub-clips
+/// to play in its place - in points and lengths only, nothing re-encoded.
+#[command]
+pub async fn remove_silences(
+    app: AppHandle,
+    path: String,
+    trim_start: Option<f64>,
+    duration: f64,
+    longer_than: f64,
+    noise_db: Option<f64>,
+) -> Result<Vec<SpeechClip>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let noise_db = noise_db.unwrap_or(DEFAULT_NOISE_DB).min(0.0);
+    let longer_than = longer_than.max(0.05);
+    let start = trim_start.unwrap_or(0.0).max(0.0);
+
+    tokio::task::spawn_blocking(move || {
+        // Detection only has to find silences as long as the ones removed
+        let detection = detect_silences_sync(&path, &ffmpeg_path, &ffprobe_path, noise_db, longer_than)?;
+        Ok(speech_clips(&detection.silences, start, start + duration, longer_than))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(silences: &[SilentRange]) -> Vec<(f64, f64)> {
+        silences.iter().map(|s| (s.start, s.end)).collect()
+    }
+
+    fn clips(clips: &[SpeechClip]) -> Vec<(f64, f64)> {
+        clips.iter().map(|c| (round(c.trim_start), round(c.duration))).collect()
+    }
+
+    fn round(value: f64) -> f64 {
+        (value * 1000.0).round() / 1000.0
+    }
+
+    fn silence(start: f64, end: f64) -> SilentRange {
+        SilentRange { start, end }
+    }
+
+    #[test]
+    fn parses_silencedetect_log() {
+        let stderr = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'talk.mov':
+  Duration: 00:00:12.00, start: 0.000000, bitrate: 2501 kb/s
+[silencedetect @ 0x600002a3c000] silence_start: -0.0213333
+[silencedetect @ 0x600002a3c000] silence_end: 1.20533 | silence_duration: 1.22667
+[silencedetect @ 0x600002a3c000] silence_start: 4.5
+[silencedetect @ 0x600002a3c000] silence_end: 6.25 | silence_duration: 1.75
+size=N/A time=00:00:12.00 bitrate=N/A speed= 512x
+";
+        assert_eq!(ranges(&parse_silences(stderr, 12.0)), vec![(0.0, 1.20533), (4.5, 6.25)]);
+    }
+
+    #[test]
+    fn runs_unterminated_silence_to_the_end() {
+        let stderr = "\
+[silencedetect @ 0x1] silence_start: 2
+[silencedetect @ 0x1] silence_end: 3 | silence_duration: 1
+[silencedetect @ 0x1] silence_start: 9.4
+";
+        assert_eq!(ranges(&parse_silences(stderr, 10.0)), vec![(2.0, 3.0), (9.4, 10.0)]);
+        // Unknown duration never gives a backwards range
+        assert_eq!(ranges(&parse_silences(stderr, 0.0)), vec![(2.0, 3.0), (9.4, 9.4)]);
+    }
+
+    #[test]
+    fn suggests_trimming_silent_ends() {
+        let silences = [silence(0.0, 1.5), silence(4.0, 5.0), silence(8.0, 10.0)];
+        let trim = suggest_trim(&silences, 10.0).unwrap();
+        assert_eq!((round(trim.trim_start), round(trim.duration)), (1.35, 6.8));
+        assert!(suggest_trim(&[silence(4.0, 5.0)], 10.0).is_none());
+        assert!(suggest_trim(&[], 10.0).is_none());
+    }
+
+    #[test]
+    fn nothing_to_keep_when_silence_covers_the_clip() {
+        let silences = parse_silences("[silencedetect @ 0x1] silence_start: 0\n", 10.0);
+        assert_eq!(ranges(&silences), vec![(0.0, 10.0)]);
+        assert!(suggest_trim(&silences, 10.0).is_none());
+        assert!(speech_clips(&silences, 0.0, 10.0, 0.5).is_empty());
+    }
+
+    #[test]
+    fn cuts_silences_longer_than_the_limit() {
+        let silences = [silence(2.0, 4.0), silence(6.0, 6.4), silence(8.0, 12.0)];
+        assert_eq!(clips(&speech_clips(&silences, 0.0, 10.0, 0.5)), vec![(0.0, 2.15), (3.85, 4.3)]);
+        // Every silence is shorter than longer_than, so the clip stays whole
+        assert_eq!(clips(&speech_clips(&silences, 0.0, 10.0, 5.0)), vec![(0.0, 10.0)]);
+    }
+
+    #[test]
+    fn drops_speech_shorter_than_min_clip() {
+        let silences = [silence(0.1, 3.0), silence(5.0, 9.9)];
+        assert_eq!(clips(&speech_clips(&silences, 0.0, 10.0, 0.5)), vec![(2.85, 2.3)]);
+        // Only the part inside start..end counts
+        assert_eq!(clips(&speech_clips(&silences, 4.0, 6.0, 0.5)), vec![(4.0, 1.15)]);
+    }
+}
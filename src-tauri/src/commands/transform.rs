@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+
+/// Part of a source frame, in pixels of the picture as displayed (after
+/// EXIF or container orientation)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Where the crop window sits `time` seconds into the source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CropKeyframe {
+    pub time: f64,
+    pub rect: CropRect,
+}
+
+/// Clockwise quarter turns. Sent as degrees; anything but 0, 90, 180 or
+/// 270 fails the payload instead of quietly not rotating.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum Rotation {
+    #[default]
+    None,
+    Clockwise,
+    UpsideDown,
+    Anticlockwise,
+}
+
+impl TryFrom<u32> for Rotation {
+    type Error = String;
+
+    fn try_from(degrees: u32) -> Result<Self, Self::Error> {
+        match degrees {
+            0 => Ok(Rotation::None),
+            90 => Ok(Rotation::Clockwise),
+            180 => Ok(Rotation::UpsideDown),
+            270 => Ok(Rotation::Anticlockwise),
+            _ => Err(format!("Invalid rotation: {} degrees (must be 0, 90, 180 or 270)", degrees)),
+        }
+    }
+}
+
+impl From<Rotation> for u32 {
+    fn from(rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::None => 0,
+            Rotation::Clockwise => 90,
+            Rotation::UpsideDown => 180,
+            Rotation::Anticlockwise => 270,
+        }
+    }
+}
+
+/// Per-clip framing, applied in order: crop, rotate, flip, then fit into
+/// the export frame scaled by `zoom` and shifted by `position_*`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipTransform {
+    #[serde(default)]
+    pub crop: Option<CropRect>,
+    #[serde(default)]
+    pub crop_keyframes: Vec<CropKeyframe>, // replaces `crop`; the window moves, sized by the first keyframe
+    #[serde(default)]
+    pub rotation: Rotation,
+    #[serde(default)]
+    pub flip_horizontal: bool,
+    #[serde(default)]
+    pub flip_vertical: bool,
+    #[serde(default = "default_zoom")]
+    pub zoom: f64, // 1 = fit inside the frame, larger crops into it
+    #[serde(default)]
+    pub position_x: f64, // -1 (left edge) to 1 (right edge), 0 = centred
+    #[serde(default)]
+    pub position_y: f64, // -1 (top edge) to 1 (bottom edge)
+}
+
+const MIN_ZOOM: f64 = 0.1;
+const MAX_ZOOM: f64 = 10.0;
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+impl Default for ClipTransform {
+    fn default() -> Self {
+        Self {
+            crop: None,
+            crop_keyframes: Vec::new(),
+            rotation: Rotation::None,
+            flip_horizontal: false,
+            flip_vertical: false,
+            zoom: default_zoom(),
+            position_x: 0.0,
+            position_y: 0.0,
+        }
+    }
+}
+
+/// Piecewise-linear ffmpeg expression in `t` through (time, value) points,
+/// holding the first and last values outside them
+fn keyframe_expr(points: &[(f64, f64)], t: &str) -> String {
+    let Some(&(_, last)) = points.last() else {
+        return "0".to_string();
+    };
+    let mut expr = format!("{:.5}", last);
+    for pair in points.windows(2).rev() {
+        let ((t0, v0), (t1, v1)) = (pair[0], pair[1]);
+        let span = (t1 - t0).max(0.001);
+        expr = format!(
+            "if(lt({t},{t1:.3}),{v0:.5}+{dv:.5}*clip(({t}-{t0:.3})/{span:.3},0,1),{expr})",
+            dv = v1 - v0,
+        );
+    }
+    expr
+}
+
+impl ClipTransform {
+    fn zoom(&self) -> f64 {
+        self.zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+    }
+
+    fn keyframes(&self) -> Vec<CropKeyframe> {
+        if self.crop_keyframes.is_empty() {
+            return self.crop.map(|rect| CropKeyframe { time: 0.0, rect }).into_iter().collect();
+        }
+        let mut keyframes = self.crop_keyframes.clone();
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        keyframes
+    }
+
+    pub fn has_crop(&self) -> bool {
+        self.crop.is_some() || !self.crop_keyframes.is_empty()
+    }
+
+    fn has_fit_adjustment(&self) -> bool {
+        (self.zoom() - 1.0).abs() > f64::EPSILON || self.position_x != 0.0 || self.position_y != 0.0
+    }
+
+    /// Whether the transform leaves the clip as plain centre-fit would
+    pub fn is_identity(&self) -> bool {
+        !self.has_crop()
+            && self.rotation == Rotation::None
+            && !self.flip_horizontal
+            && !self.flip_vertical
+            && !self.has_fit_adjustment()
+    }
+
+    /// Same transform with keyframes `offset` seconds earlier, for a clip
+    /// whose input is seeked `offset` seconds in
+    pub fn shifted(&self, offset: f64) -> Self {
+        let mut shifted = self.clone();
+        for keyframe in &mut shifted.crop_keyframes {
+            keyframe.time -= offset;
+        }
+        shifted
+    }
+
+    /// How much larger than the export frame a still has to be for the
+    /// cropped and zoomed picture to keep full detail
+    pub fn detail_scale(&self, source_width: Option<u32>, source_height: Option<u32>) -> f64 {
+        let crop_fraction = match (source_width, source_height) {
+            (Some(w), Some(h)) if w > 0 && h > 0 => self
+                .keyframes()
+                .iter()
+                .map(|k| (k.rect.width / w as f64).min(k.rect.height / h as f64))
+                .fold(1.0_f64, f64::min),
+            _ => 1.0,
+        };
+        self.zoom().max(1.0) / crop_fraction.max(0.01)
+    }
+
+    /// Crop, rotate and flip filters, each followed by a comma. The crop is
+    /// given as a share of the input size, so it still lines up when the
+    /// source was downscaled or had its pixels squared first. `loop_duration`
+    /// wraps keyframe time for inputs that are read more than once.
+    pub fn source_filters(
+        &self,
+        source_width: Option<u32>,
+        source_height: Option<u32>,
+        loop_duration: Option<f64>,
+    ) -> String {
+        let mut filters = String::new();
+
+        let keyframes = self.keyframes();
+        if let (Some(first), Some(w), Some(h)) = (keyframes.first(), source_width, source_height) {
+            let (w, h) = (w.max(1) as f64, h.max(1) as f64);
+            let t = match loop_duration {
+                Some(d) if d > 0.0 => format!("mod(t,{:.3})", d),
+                _ => "t".to_string(),
+            };
+            let xs: Vec<(f64, f64)> = keyframes.iter().map(|k| (k.time, k.rect.x / w)).collect();
+            let ys: Vec<(f64, f64)> = keyframes.iter().map(|k| (k.time, k.rect.y / h)).collect();
+            filters.push_str(&format!(
+                "crop=w='trunc(iw*{:.5}/2)*2':h='trunc(ih*{:.5}/2)*2':x='iw*({})':y='ih*({})',",
+                (first.rect.width / w).clamp(0.0, 1.0),
+                (first.rect.height / h).clamp(0.0, 1.0),
+                keyframe_expr(&xs, &t),
+                keyframe_expr(&ys, &t),
+            ));
+        }
+
+        match self.rotation {
+            Rotation::None => {}
+            Rotation::Clockwise => filters.push_str("transpose=clock,"),
+            Rotation::UpsideDown => filters.push_str("hflip,vflip,"),
+            Rotation::Anticlockwise => filters.push_str("transpose=cclock,"),
+        }
+        if self.flip_horizontal {
+            filters.push_str("hflip,");
+        }
+        if self.flip_vertical {
+            filters.push_str("vflip,");
+        }
+        filters
+    }
+
+    /// Whether the rotation turns the picture on its side
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(self.rotation, Rotation::Clockwise | Rotation::Anticlockwise)
+    }
+}
+
+/// Scale and pad (or crop) the picture into the `width`x`height` frame.
+/// Without a zoom or position this is the plain centred fit.
+pub fn fit_filters(transform: Option<&ClipTransform>, width: u32, height: u32) -> String {
+    let Some(transform) = transform.filter(|t| t.has_fit_adjustment()) else {
+        return format!(
+            "scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black",
+            width, height, width, height
+        );
+    };
+
+    let zoom = transform.zoom();
+    let box_width = ((width as f64 * zoom / 2.0).round() as u32 * 2).max(2);
+    let box_height = ((height as f64 * zoom / 2.0).round() as u32 * 2).max(2);
+    let ax = (transform.position_x.clamp(-1.0, 1.0) + 1.0) / 2.0;
+    let ay = (transform.position_y.clamp(-1.0, 1.0) + 1.0) / 2.0;
+
+    // Smaller than the frame: pad around it; larger: crop the visible part
+    format!(
+        "scale={bw}:{bh}:force_original_aspect_ratio=decrease,\
+         pad='max(iw,{w})':'max(ih,{h})':'(ow-iw)*{ax:.4}':'(oh-ih)*{ay:.4}':black,\
+         crop={w}:{h}:'(iw-ow)*{ax:.4}':'(ih-oh)*{ay:.4}'",
+        bw = box_width,
+        bh = box_height,
+        w = width,
+        h = height,
+    )
+}
+
+/// Fixed crop to `rect`, given as a share of the input size like the
+/// keyframed crop
+pub fn crop_filter(rect: &CropRect, source_width: u32, source_height: u32) -> String {
+    let (w, h) = (source_width.max(1) as f64, source_height.max(1) as f64);
+    format!(
+        "crop=w='trunc(iw*{:.5}/2)*2':h='trunc(ih*{:.5}/2)*2':x='iw*{:.5}':y='ih*{:.5}',",
+        (rect.width / w).clamp(0.0, 1.0),
+        (rect.height / h).clamp(0.0, 1.0),
+        (rect.x / w).clamp(0.0, 1.0),
+        (rect.y / h).clamp(0.0, 1.0),
+    )
+}
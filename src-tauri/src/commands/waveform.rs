@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::{command, AppHandle};
+
+use super::cache::{cache_dir, cache_key, read_cached_json, write_cached_json};
+use super::sidecar::{get_ffmpeg_path, get_ffprobe_path};
+
+/// Audio is decoded to mono at this rate - plenty for drawing peaks
+const WAVEFORM_SAMPLE_RATE: u32 = 8000;
+const DEFAULT_WAVEFORM_POINTS: u32 = 1000;
+const MAX_WAVEFORM_POINTS: u32 = 20000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioWaveform {
+    pub has_audio: bool,
+    pub duration: f64,         // seconds of decoded audio
+    pub sample_rate: u32,      // rate the peaks were computed at
+    pub samples_per_peak: u32, // decoded samples folded into each peak
+    pub min: Vec<f32>,         // per-bucket minimum, -1.0..=1.0
+    pub max: Vec<f32>,         // per-bucket maximum, -1.0..=1.0
+}
+
+impl AudioWaveform {
+    fn empty() -> Self {
+        AudioWaveform {
+            has_audio: false,
+            duration: 0.0,
+            sample_rate: WAVEFORM_SAMPLE_RATE,
+            samples_per_peak: 0,
+            min: Vec::new(),
+            max: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeAudioFormat {
+    duration: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeAudioStream {
+    codec_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeAudio {
+    format: Option<ProbeAudioFormat>,
+    streams: Option<Vec<ProbeAudioStream>>,
+}
+
+/// Returns (has_audio, container duration)
+pub fn probe_audio(ffprobe_path: &Path, path: &str) -> Result<(bool, Option<f64>), String> {
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-select_streams", "a",
+            "-show_entries", "format=duration:stream=codec_type",
+            path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    let probe: ProbeAudio = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let has_audio = probe
+        .streams
+        .unwrap_or_default()
+        .iter()
+        .any(|s| s.codec_type.as_deref() == Some("audio"));
+    let duration = probe
+        .format
+        .and_then(|f| f.duration)
+        .and_then(|d| d.parse::<f64>().ok())
+        .filter(|d| *d > 0.0);
+
+    Ok((has_audio, duration))
+}
+
+/// Decode audio with ffmpeg and fold it into min/max peak buckets while
+/// streaming, so long recordings never sit in memory as raw samples
+fn extract_waveform_sync(
+    path: &str,
+    ffmpeg_path: &Path,
+    ffprobe_path: &Path,
+    points: u32,
+) -> Result<AudioWaveform, String> {
+    let (has_audio, duration) = probe_audio(ffprobe_path, path)?;
+    if !has_audio {
+        return Ok(AudioWaveform::empty());
+    }
+
+    let waveforms_dir = cache_dir("waveforms")?;
+    let cache_path = waveforms_dir.join(format!("{}.json", cache_key(path, &format!("waveform-{}", points))));
+
+    if let Some(cached) = read_cached_json::<AudioWaveform>(&cache_path) {
+        return Ok(cached);
+    }
+
+    // Without a known duration fall back to ~10ms buckets
+    let samples_per_peak = match duration {
+        Some(d) => ((d * WAVEFORM_SAMPLE_RATE as f64) / points as f64).ceil().max(1.0) as u32,
+        None => WAVEFORM_SAMPLE_RATE / 100,
+    };
+
+    let mut child = Command::new(ffmpeg_path)
+        .args([
+            "-hide_banner",
+            "-v", "error",
+            "-i", path,
+            "-map", "0:a:0",
+            "-vn",
+            "-ac", "1",
+            "-ar", &WAVEFORM_SAMPLE_RATE.to_string(),
+            "-f", "f32le",
+            "-",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    let Some(mut stdout) = child.stdout.take() else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err("Failed to read ffmpeg output".to_string());
+    };
+
+    let mut min = Vec::with_capacity(points as usize);
+    let mut max = Vec::with_capacity(points as usize);
+    let (mut bucket_min, mut bucket_max, mut in_bucket) = (0.0f32, 0.0f32, 0u32);
+    let mut total_samples: u64 = 0;
+
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut carry: Vec<u8> = Vec::with_capacity(4);
+    loop {
+        let read = match stdout.read(&mut buf) {
+            Ok(read) => read,
+            Err(e) => {
+                // Don't leave a zombie ffmpeg behind
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Failed to read ffmpeg output: {}", e));
+            }
+        };
+        if read == 0 {
+            break;
+        }
+
+        // Samples can straddle reads - keep the partial bytes for next time
+        carry.extend_from_slice(&buf[..read]);
+        let whole = carry.len() / 4 * 4;
+        for chunk in carry[..whole].chunks_exact(4) {
+            let sample = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            if in_bucket == 0 {
+                bucket_min = sample;
+                bucket_max = sample;
+            } else {
+                bucket_min = bucket_min.min(sample);
+                bucket_max = bucket_max.max(sample);
+            }
+            in_bucket += 1;
+            total_samples += 1;
+
+            if in_bucket == samples_per_peak {
+                min.push(bucket_min.clamp(-1.0, 1.0));
+                max.push(bucket_max.clamp(-1.0, 1.0));
+                in_bucket = 0;
+            }
+        }
+        carry.drain(..whole);
+    }
+
+    if in_bucket > 0 {
+        min.push(bucket_min.clamp(-1.0, 1.0));
+        max.push(bucket_max.clamp(-1.0, 1.0));
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !status.success() && total_samples == 0 {
+        return Err("Failed to decode audio".to_string());
+    }
+
+    let waveform = AudioWaveform {
+        has_audio: true,
+        duration: total_samples as f64 / WAVEFORM_SAMPLE_RATE as f64,
+        sample_rate: WAVEFORM_SAMPLE_RATE,
+        samples_per_peak,
+        min,
+        max,
+    };
+
+    // Don't cache peaks from a decode that bailed out part way
+    if status.success() {
+        write_cached_json(&cache_path, &waveform);
+    }
+
+    Ok(waveform)
+}
+
+/// Get min/max audio peaks for a media file at roughly `points` buckets.
+/// Files without an audio stream return `has_audio: false` and no peaks.
+#[command]
+pub async fn get_audio_waveform(
+    app: AppHandle,
+    path: String,
+    points: Option<u32>,
+) -> Result<AudioWaveform, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let points = points
+        .unwrap_or(DEFAULT_WAVEFORM_POINTS)
+        .clamp(1, MAX_WAVEFORM_POINTS);
+
+    tokio::task::spawn_blocking(move || extract_waveform_sync(&path, &ffmpeg_path, &ffprobe_path, points))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
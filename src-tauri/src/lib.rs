@@ -1,13 +1,25 @@
 mod commands;
 
 use commands::{
+    black_bars::detect_black_bars,
+    cache::THUMB_SCHEME,
     ffmpeg::{export_video, get_video_duration},
     files::{
         check_ffmpeg, cleanup_temp_dir, create_temp_dir, file_exists,
         generate_output_filename, get_downloads_path, get_ffmpeg_version,
         get_file_info, get_home_path, open_in_finder,
     },
+    frames::{generate_filmstrip, get_frame_at, save_frame_at},
+    live_photo::{find_live_photos, get_live_photo_metadata},
     metadata::{get_media_metadata, get_media_metadata_batch, generate_thumbnail, generate_thumbnails_batch},
+    preview::{render_preview_clip, render_preview_frame},
+    protocol::serve_cached_file,
+    proxy::{cleanup_proxies, get_proxy_status, request_proxies, ProxyManager},
+    reframe::analyze_reframe,
+    scenes::{detect_scenes, split_at_scenes},
+    sequence::{find_image_sequences, get_image_sequence_metadata},
+    silence::{detect_silences, remove_silences},
+    waveform::get_audio_waveform,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -17,6 +29,9 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_notification::init())
+        .manage(ProxyManager::default())
+        // Serves cached thumbnails/frames without base64 over IPC
+        .register_uri_scheme_protocol(THUMB_SCHEME, |_ctx, request| serve_cached_file(&request))
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -47,6 +62,30 @@ pub fn run() {
             get_media_metadata_batch,
             generate_thumbnail,
             generate_thumbnails_batch,
+            find_image_sequences,
+            get_image_sequence_metadata,
+            find_live_photos,
+            get_live_photo_metadata,
+            // Frame commands
+            generate_filmstrip,
+            get_frame_at,
+            save_frame_at,
+            // Preview commands
+            render_preview_frame,
+            render_preview_clip,
+            // Proxy commands
+            request_proxies,
+            get_proxy_status,
+            cleanup_proxies,
+            // Analysis commands
+            analyze_reframe,
+            detect_black_bars,
+            detect_scenes,
+            split_at_scenes,
+            detect_silences,
+            remove_silences,
+            // Audio commands
+            get_audio_waveform,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -3,6 +3,7 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { MediaTimeline } from './components/MediaTimeline';
 import { PreviewPanel } from './components/PreviewPanel';
+import { ClipSettings } from './components/ClipSettings';
 import { CoverTextEditor } from './components/CoverTextEditor';
 import { PresetSelector } from './components/PresetSelector';
 import { ExportButton } from './components/ExportButton';
@@ -11,11 +12,11 @@ import { useFFmpeg } from './hooks/useFFmpeg';
 import { useMediaFiles } from './hooks/useMediaFiles';
 import { useExport } from './hooks/useExport';
 import { useProjectStore, useTotalDuration } from './stores/projectStore';
-import { isSupportedMedia, ALL_EXTENSIONS } from './utils/mediaUtils';
+import { ALL_EXTENSIONS, getTimelineDuration } from './utils/mediaUtils';
 
 function App() {
   const { isAvailable: ffmpegAvailable } = useFFmpeg();
-  const { processFiles } = useMediaFiles();
+  const { processFiles, error: importError, rejected, clearError } = useMediaFiles();
   const { canExport, startExport } = useExport();
   const { isPlaying, setIsPlaying, previewTime, setPreviewTime, resetProject } = useProjectStore();
   const totalDuration = useTotalDuration();
@@ -39,10 +40,10 @@ function App() {
           setIsDraggingOver(false);
         } else if (event.payload.type === 'drop') {
           setIsDraggingOver(false);
+          // Extensions can be missing or wrong - the backend sniffs contents
           const paths = event.payload.paths;
-          const validPaths = paths.filter((path: string) => isSupportedMedia(path));
-          if (validPaths.length > 0) {
-            processFiles(validPaths);
+          if (paths.length > 0) {
+            processFiles(paths).catch(() => {});
           }
         }
       });
@@ -101,7 +102,7 @@ function App() {
           const current = state.previewTime;
           // Calculate duration from current state
           const coverDur = state.cover.enabled && state.cover.text ? state.cover.duration : 0;
-          const mediaDur = state.mediaFiles.reduce((sum, f) => sum + f.duration, 0);
+          const mediaDur = state.mediaFiles.reduce((sum, f) => sum + getTimelineDuration(f), 0);
           const duration = coverDur + mediaDur;
           setPreviewTime(Math.min(duration, current + 5));
         }, 200);
@@ -156,6 +157,10 @@ function App() {
             name: 'Media Files',
             extensions: ALL_EXTENSIONS.map((ext) => ext.replace('.', '')),
           },
+          {
+            name: 'All Files',
+            extensions: ['*'],
+          },
         ],
       });
 
@@ -200,6 +205,9 @@ function App() {
       {/* Right panel - Controls (full height) */}
       <div className="w-80 border-l border-gray-800 bg-gray-900/30 flex flex-col overflow-hidden">
         <div className="flex-1 overflow-y-auto p-4 space-y-6">
+          {/* Settings for the clip selected in the timeline */}
+          <ClipSettings />
+
           {/* Cover Text Editor */}
           <CoverTextEditor />
 
@@ -231,6 +239,34 @@ function App() {
         </div>
       )}
 
+      {/* Import problems */}
+      {(importError || rejected.length > 0) && (
+        <div className="fixed bottom-4 right-4 w-96 p-4 bg-amber-500/20 border border-amber-500/50 rounded-lg z-40">
+          <div className="flex items-start justify-between gap-3">
+            <div className="min-w-0">
+              <h3 className="text-amber-400 font-medium text-sm">
+                {importError ?? `${rejected.length} file${rejected.length !== 1 ? 's' : ''} couldn't be imported`}
+              </h3>
+              <ul className="mt-2 space-y-1 max-h-40 overflow-y-auto">
+                {rejected.map((file) => (
+                  <li key={file.path} className="text-xs text-amber-300/80">
+                    <span className="font-medium">{file.path.split(/[\\/]/).pop()}</span>: {file.reason}
+                  </li>
+                ))}
+              </ul>
+            </div>
+            <button
+              onClick={clearError}
+              className="text-amber-400 hover:text-amber-300 flex-shrink-0"
+            >
+              <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 18L18 6M6 6l12 12" />
+              </svg>
+            </button>
+          </div>
+        </div>
+      )}
+
       {/* Progress Modal - always rendered, handles its own visibility */}
       <ProgressModal
         outputPath={outputPath}
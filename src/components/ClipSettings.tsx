@@ -0,0 +1,523 @@
+import { useState } from 'react';
+import type { ReactNode } from 'react';
+import { useProjectStore, usePlayheadOffset } from '../stores/projectStore';
+import { canProxy } from '../hooks/useProxies';
+import { canRemoveSilences, useSilences } from '../hooks/useSilences';
+import type { ClipTransform, CropRect, LivePhotoMode, MediaFile, PlaybackDirection } from '../types';
+import {
+  MAX_SPEED,
+  MIN_SPEED,
+  formatDuration,
+  getSourceTime,
+  getSpeed,
+  getTimelineDuration,
+  livePhotoModeUpdate,
+} from '../utils/mediaUtils';
+
+const MAX_LOOPS = 20;
+const MIN_SEQUENCE_FPS = 1;
+const MAX_SEQUENCE_FPS = 60;
+const SPEED_STEP = 0.25;
+// Within the backend's 0.1-10 zoom range
+const MIN_ZOOM = 0.5;
+const MAX_ZOOM = 4;
+// Crop insets, as a percentage of the frame on each side
+const CROP_STEP = 5;
+const MAX_CROP_INSET = 45;
+
+interface StepperProps {
+  label: string;
+  value: number;
+  min: number;
+  max: number;
+  step: number;
+  format?: (value: number) => string;
+  onChange: (value: number) => void;
+}
+
+function Stepper({ label, value, min, max, step, format, onChange }: StepperProps) {
+  // Rounded so fractional steps don't build up float error
+  const clamp = (v: number) => Math.min(Math.max(Number((Math.round(v / step) * step).toFixed(4)), min), max);
+
+  return (
+    <div className="flex items-center justify-between">
+      <span className="text-xs text-gray-400">{label}</span>
+      <div className="flex items-center gap-1">
+        <button
+          onClick={() => onChange(clamp(value - step))}
+          disabled={value <= min}
+          className="w-6 h-6 rounded-md bg-gray-800 border border-gray-700 text-gray-300 text-xs hover:border-gray-600 disabled:opacity-40"
+        >
+          −
+        </button>
+        <span className="w-12 text-center text-xs text-white font-mono">
+          {format ? format(value) : value}
+        </span>
+        <button
+          onClick={() => onChange(clamp(value + step))}
+          disabled={value >= max}
+          className="w-6 h-6 rounded-md bg-gray-800 border border-gray-700 text-gray-300 text-xs hover:border-gray-600 disabled:opacity-40"
+        >
+          +
+        </button>
+      </div>
+    </div>
+  );
+}
+
+interface SectionProps {
+  title: string;
+  children: ReactNode;
+}
+
+function Section({ title, children }: SectionProps) {
+  return (
+    <div className="space-y-2">
+      <h4 className="text-xs text-gray-400">{title}</h4>
+      {children}
+    </div>
+  );
+}
+
+interface SegmentedOption<T extends string> {
+  value: T;
+  label: string;
+  title: string;
+}
+
+interface SegmentedProps<T extends string> {
+  options: SegmentedOption<T>[];
+  value: T;
+  onChange: (value: T) => void;
+}
+
+function Segmented<T extends string>({ options, value, onChange }: SegmentedProps<T>) {
+  return (
+    <div className="flex gap-1 p-1 bg-gray-800/50 rounded-lg border border-gray-700">
+      {options.map((option) => (
+        <button
+          key={option.value}
+          title={option.title}
+          onClick={() => onChange(option.value)}
+          className={`
+            flex-1 py-1.5 rounded-md text-xs font-medium transition-all
+            ${value === option.value
+              ? 'bg-indigo-500/20 text-white'
+              : 'text-gray-400 hover:text-white'
+            }
+          `}
+        >
+          {option.label}
+        </button>
+      ))}
+    </div>
+  );
+}
+
+interface ClipControlsProps {
+  file: MediaFile;
+  onUpdate: (updates: Partial<MediaFile>) => void;
+}
+
+/**
+ * Animations play once by default; more loops lengthen the clip
+ */
+function AnimationControls({ file, onUpdate }: ClipControlsProps) {
+  return (
+    <Section title="Animation">
+      <Stepper
+        label="Loops"
+        value={file.loops ?? 1}
+        min={1}
+        max={MAX_LOOPS}
+        step={1}
+        format={(v) => `${v}×`}
+        onChange={(loops) => onUpdate({ loops: loops > 1 ? loops : undefined })}
+      />
+    </Section>
+  );
+}
+
+/**
+ * A timelapse lasts as long as its frames take at the chosen frame rate
+ */
+function SequenceControls({ file, onUpdate }: ClipControlsProps) {
+  const sequence = file.imageSequence;
+  if (!sequence) return null;
+
+  return (
+    <Section title="Timelapse">
+      <Stepper
+        label="Frame rate"
+        value={sequence.framerate}
+        min={MIN_SEQUENCE_FPS}
+        max={MAX_SEQUENCE_FPS}
+        step={1}
+        format={(v) => `${v} fps`}
+        onChange={(framerate) => onUpdate({
+          imageSequence: { ...sequence, framerate },
+          duration: sequence.frameCount / framerate,
+          framerate,
+        })}
+      />
+      <p className="text-[10px] text-gray-500">{sequence.frameCount} frames</p>
+    </Section>
+  );
+}
+
+const DIRECTION_OPTIONS: SegmentedOption<PlaybackDirection>[] = [
+  { value: 'forward', label: 'Forward', title: 'Play as recorded' },
+  { value: 'reverse', label: 'Reverse', title: 'Play backwards' },
+  { value: 'boomerang', label: 'Boomerang', title: 'Play forwards, then backwards' },
+];
+
+/**
+ * Speed, direction, loops and sound of a plain video
+ */
+function PlaybackControls({ file, onUpdate }: ClipControlsProps) {
+  return (
+    <Section title="Playback">
+      <Stepper
+        label="Speed"
+        value={getSpeed(file)}
+        min={MIN_SPEED}
+        max={MAX_SPEED}
+        step={SPEED_STEP}
+        format={(v) => `${v}×`}
+        onChange={(speed) => onUpdate({ speed: speed !== 1 ? speed : undefined })}
+      />
+      <Segmented
+        options={DIRECTION_OPTIONS}
+        value={file.playback ?? 'forward'}
+        onChange={(playback) => onUpdate({ playback: playback !== 'forward' ? playback : undefined })}
+      />
+      <Stepper
+        label="Loops"
+        value={file.loops ?? 1}
+        min={1}
+        max={MAX_LOOPS}
+        step={1}
+        format={(v) => `${v}×`}
+        onChange={(loops) => onUpdate({ loops: loops > 1 ? loops : undefined })}
+      />
+      <label className="flex items-center justify-between gap-2 cursor-pointer">
+        <span className="text-xs text-gray-400">Mute audio</span>
+        <input
+          type="checkbox"
+          checked={file.muteAudio ?? false}
+          onChange={(e) => onUpdate({ muteAudio: e.target.checked || undefined })}
+          className="accent-indigo-500"
+        />
+      </label>
+    </Section>
+  );
+}
+
+/**
+ * Silence tools for one clip: trim quiet lead-in and tail, or cut every
+ * long pause (which splits the clip)
+ */
+function AudioControls({ file }: ClipControlsProps) {
+  const { processingId, error, trimSilentEnds, removeSilences, processFile } = useSilences();
+  const isProcessing = processingId === file.id;
+
+  const trimEnds = () => processFile(file, async (f) => {
+    if (!(await trimSilentEnds(f))) {
+      throw new Error('No silence at either end');
+    }
+  });
+
+  return (
+    <Section title="Audio">
+      <div className="flex gap-2">
+        <button
+          onClick={trimEnds}
+          disabled={isProcessing}
+          title="Move the in and out points past silence at the start and end"
+          className="flex-1 py-1.5 rounded-md bg-gray-800 border border-gray-700 text-gray-300 text-xs hover:border-gray-600 disabled:opacity-40"
+        >
+          Trim silent ends
+        </button>
+        <button
+          onClick={() => processFile(file, removeSilences)}
+          disabled={isProcessing}
+          title="Cut silences longer than a second, splitting the clip"
+          className="flex-1 py-1.5 rounded-md bg-gray-800 border border-gray-700 text-gray-300 text-xs hover:border-gray-600 disabled:opacity-40"
+        >
+          Cut silences
+        </button>
+      </div>
+      {isProcessing && <p className="text-[10px] text-gray-500">Listening for silence...</p>}
+      {error && <p className="text-[10px] text-amber-400">{error}</p>}
+    </Section>
+  );
+}
+
+type Rotation = NonNullable<ClipTransform['rotation']>;
+
+const ROTATION_OPTIONS: SegmentedOption<`${Rotation}`>[] = [
+  { value: '0', label: '0°', title: 'As shot' },
+  { value: '90', label: '90°', title: 'Turn a quarter clockwise' },
+  { value: '180', label: '180°', title: 'Turn upside down' },
+  { value: '270', label: '270°', title: 'Turn a quarter anticlockwise' },
+];
+
+interface CropInsets {
+  left: number;
+  right: number;
+  top: number;
+  bottom: number;
+}
+
+const toInsets = (rect: CropRect | undefined, width: number, height: number): CropInsets => {
+  if (!rect) return { left: 0, right: 0, top: 0, bottom: 0 };
+  const percent = (v: number) => Math.round(v * 100);
+  return {
+    left: percent(rect.x / width),
+    right: percent(1 - (rect.x + rect.width) / width),
+    top: percent(rect.y / height),
+    bottom: percent(1 - (rect.y + rect.height) / height),
+  };
+};
+
+const toRect = (insets: CropInsets, width: number, height: number): CropRect => ({
+  x: Math.round((width * insets.left) / 100),
+  y: Math.round((height * insets.top) / 100),
+  width: Math.round((width * (100 - insets.left - insets.right)) / 100),
+  height: Math.round((height * (100 - insets.top - insets.bottom)) / 100),
+});
+
+const CROP_SIDES: { side: keyof CropInsets; label: string }[] = [
+  { side: 'left', label: 'Left' },
+  { side: 'right', label: 'Right' },
+  { side: 'top', label: 'Top' },
+  { side: 'bottom', label: 'Bottom' },
+];
+
+/**
+ * Crop, rotation, flips and placement in the frame. With keyframes the crop
+ * window moves: the crop controls edit the selected keyframe, and new ones
+ * are added where the playhead is.
+ */
+function FramingControls({ file, onUpdate }: ClipControlsProps) {
+  const playheadOffset = usePlayheadOffset(file.id);
+  const [selectedKeyframe, setSelectedKeyframe] = useState(0);
+  if (!file.width || !file.height) return null;
+  const { width, height } = file;
+
+  const transform: ClipTransform = file.transform ?? {};
+  const keyframes = transform.cropKeyframes ?? [];
+  const keyframeIndex = Math.min(selectedKeyframe, keyframes.length - 1);
+  const editedRect = keyframes.length > 0 ? keyframes[keyframeIndex].rect : transform.crop;
+  const insets = toInsets(editedRect, width, height);
+
+  const updateTransform = (changes: Partial<ClipTransform>) => {
+    onUpdate({ transform: { ...transform, ...changes } });
+  };
+
+  const setInset = (side: keyof CropInsets, value: number) => {
+    const next = { ...insets, [side]: value };
+    const isFullFrame = Object.values(next).every((v) => v === 0);
+    const rect = toRect(next, width, height);
+    if (keyframes.length > 0) {
+      updateTransform({
+        cropKeyframes: keyframes.map((k, i) => (i === keyframeIndex ? { ...k, rect } : k)),
+      });
+    } else {
+      updateTransform({ crop: isFullFrame ? undefined : rect });
+    }
+  };
+
+  // Keyframe times are seconds into the source, like the analysed ones
+  const addKeyframe = () => {
+    const time = playheadOffset !== null ? getSourceTime(file, playheadOffset) : (file.trimStart ?? 0);
+    const rect = editedRect ?? { x: 0, y: 0, width, height };
+    const next = [...keyframes.filter((k) => Math.abs(k.time - time) > 0.05), { time, rect }]
+      .sort((a, b) => a.time - b.time);
+    updateTransform({ crop: undefined, cropKeyframes: next });
+    setSelectedKeyframe(next.findIndex((k) => k.time === time));
+  };
+
+  const removeKeyframe = (index: number) => {
+    const next = keyframes.filter((_, i) => i !== index);
+    // The last keyframe's window stays as a fixed crop
+    updateTransform(next.length > 0
+      ? { cropKeyframes: next }
+      : { cropKeyframes: undefined, crop: keyframes[index].rect });
+    setSelectedKeyframe(Math.max(0, Math.min(keyframeIndex, next.length - 1)));
+  };
+
+  return (
+    <Section title="Framing">
+      <Segmented
+        options={ROTATION_OPTIONS}
+        value={`${transform.rotation ?? 0}` as `${Rotation}`}
+        onChange={(rotation) => updateTransform({ rotation: Number(rotation) as Rotation })}
+      />
+      <div className="flex gap-3">
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={transform.flipHorizontal ?? false}
+            onChange={(e) => updateTransform({ flipHorizontal: e.target.checked })}
+            className="accent-indigo-500"
+          />
+          <span className="text-xs text-gray-400">Flip horizontal</span>
+        </label>
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={transform.flipVertical ?? false}
+            onChange={(e) => updateTransform({ flipVertical: e.target.checked })}
+            className="accent-indigo-500"
+          />
+          <span className="text-xs text-gray-400">Flip vertical</span>
+        </label>
+      </div>
+      <Stepper
+        label="Zoom"
+        value={transform.zoom ?? 1}
+        min={MIN_ZOOM}
+        max={MAX_ZOOM}
+        step={0.1}
+        format={(v) => `${v.toFixed(1)}×`}
+        onChange={(zoom) => updateTransform({ zoom })}
+      />
+      <Stepper
+        label="Position X"
+        value={transform.positionX ?? 0}
+        min={-1}
+        max={1}
+        step={0.1}
+        format={(v) => `${Math.round(v * 100)}%`}
+        onChange={(positionX) => updateTransform({ positionX })}
+      />
+      <Stepper
+        label="Position Y"
+        value={transform.positionY ?? 0}
+        min={-1}
+        max={1}
+        step={0.1}
+        format={(v) => `${Math.round(v * 100)}%`}
+        onChange={(positionY) => updateTransform({ positionY })}
+      />
+
+      <h4 className="text-xs text-gray-400 pt-1">
+        Crop{keyframes.length > 0 && ` - keyframe ${keyframeIndex + 1}`}
+      </h4>
+      {CROP_SIDES.map(({ side, label }) => (
+        <Stepper
+          key={side}
+          label={label}
+          value={insets[side]}
+          min={0}
+          max={MAX_CROP_INSET}
+          step={CROP_STEP}
+          format={(v) => `${v}%`}
+          onChange={(value) => setInset(side, value)}
+        />
+      ))}
+
+      <div className="space-y-1">
+        {keyframes.map((keyframe, i) => (
+          <div
+            key={i}
+            className={`flex items-center justify-between px-2 py-1 rounded-md text-xs ${
+              i === keyframeIndex ? 'bg-indigo-500/20 text-white' : 'text-gray-400'
+            }`}
+          >
+            <button onClick={() => setSelectedKeyframe(i)} className="flex-1 text-left font-mono">
+              {formatDuration(keyframe.time)}
+            </button>
+            <button
+              onClick={() => removeKeyframe(i)}
+              title="Remove keyframe"
+              className="text-gray-500 hover:text-gray-300"
+            >
+              ×
+            </button>
+          </div>
+        ))}
+        <button
+          onClick={addKeyframe}
+          disabled={playheadOffset === null && keyframes.length > 0}
+          title="Key the crop window at the playhead"
+          className="w-full py-1.5 rounded-md bg-gray-800 border border-gray-700 text-gray-300 text-xs hover:border-gray-600 disabled:opacity-40"
+        >
+          Add keyframe
+        </button>
+        {keyframes.length > 1 && (
+          <p className="text-[10px] text-gray-500">The window keeps the first keyframe's size</p>
+        )}
+      </div>
+
+      <button
+        onClick={() => onUpdate({ transform: undefined })}
+        disabled={!file.transform}
+        className="text-xs text-gray-500 hover:text-gray-300 disabled:opacity-40"
+      >
+        Reset framing
+      </button>
+    </Section>
+  );
+}
+
+const LIVE_PHOTO_OPTIONS: SegmentedOption<LivePhotoMode>[] = [
+  { value: 'still', label: 'Still', title: 'Show the photo' },
+  { value: 'motion', label: 'Motion', title: 'Play the motion clip' },
+  { value: 'still_with_motion_intro', label: 'Both', title: 'Play the motion clip, then hold the photo' },
+];
+
+function LivePhotoControls({ file, onUpdate }: ClipControlsProps) {
+  if (!file.livePhoto) return null;
+
+  return (
+    <Section title="Live Photo">
+      <Segmented
+        options={LIVE_PHOTO_OPTIONS}
+        value={file.livePhoto.mode}
+        onChange={(mode) => onUpdate(livePhotoModeUpdate(file, mode))}
+      />
+    </Section>
+  );
+}
+
+export function ClipSettings() {
+  const mediaFiles = useProjectStore((state) => state.mediaFiles);
+  const selectedMediaId = useProjectStore((state) => state.selectedMediaId);
+  const setSelectedMediaId = useProjectStore((state) => state.setSelectedMediaId);
+  const updateMediaFile = useProjectStore((state) => state.updateMediaFile);
+
+  const file = mediaFiles.find((f) => f.id === selectedMediaId);
+  if (!file) return null;
+
+  const onUpdate = (updates: Partial<MediaFile>) => updateMediaFile(file.id, updates);
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-between gap-2">
+        <h3 className="text-sm font-medium text-gray-300 truncate" title={file.name}>
+          {file.name}
+        </h3>
+        <button
+          onClick={() => setSelectedMediaId(null)}
+          className="text-gray-500 hover:text-gray-300 flex-shrink-0"
+        >
+          <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 18L18 6M6 6l12 12" />
+          </svg>
+        </button>
+      </div>
+      <p className="text-[10px] text-gray-500">
+        Plays for {formatDuration(getTimelineDuration(file))}
+      </p>
+
+      {canProxy(file) && <PlaybackControls file={file} onUpdate={onUpdate} />}
+      {canRemoveSilences(file) && <AudioControls key={file.id} file={file} onUpdate={onUpdate} />}
+      {file.isAnimated && <AnimationControls file={file} onUpdate={onUpdate} />}
+      {file.imageSequence && <SequenceControls file={file} onUpdate={onUpdate} />}
+      {file.livePhoto && <LivePhotoControls file={file} onUpdate={onUpdate} />}
+      <FramingControls key={file.id} file={file} onUpdate={onUpdate} />
+    </div>
+  );
+}
@@ -1,11 +1,17 @@
 import { useExport } from '../hooks/useExport';
-import { useTotalDuration, useMediaCount } from '../stores/projectStore';
-import { formatDuration } from '../utils/mediaUtils';
+import { useProjectStore, useTotalDuration, useMediaCount } from '../stores/projectStore';
+import { estimateExportTime, formatDuration } from '../utils/mediaUtils';
 
 export function ExportButton() {
   const { canExport, isExporting, startExport } = useExport();
   const totalDuration = useTotalDuration();
   const { total, videos, images } = useMediaCount();
+  const estimatedTime = useProjectStore((state) => estimateExportTime(
+    state.mediaFiles,
+    state.cover.text.trim() ? state.cover.duration : 0,
+    state.frameRateConversion,
+    state.deinterlaceDoubleRate
+  ));
 
   const handleExport = async () => {
     if (!canExport) return;
@@ -26,6 +32,10 @@ export function ExportButton() {
             <span className="text-xs text-gray-400">Total Duration</span>
             <span className="text-sm font-medium text-white">{formatDuration(totalDuration)}</span>
           </div>
+          <div className="flex items-center justify-between">
+            <span className="text-xs text-gray-400">Estimated Export Time</span>
+            <span className="text-xs text-gray-300">~{formatDuration(Math.max(1, estimatedTime))}</span>
+          </div>
           <div className="flex items-center justify-between">
             <span className="text-xs text-gray-400">Media Items</span>
             <span className="text-xs text-gray-300">
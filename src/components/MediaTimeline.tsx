@@ -20,16 +20,25 @@ import {
 } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
 import { useProjectStore, useTotalDuration } from '../stores/projectStore';
+import { useReframe } from '../hooks/useReframe';
+import { canSplitScenes, useScenes } from '../hooks/useScenes';
+import { canRemoveSilences, useSilences } from '../hooks/useSilences';
+import { ScenePicker } from './ScenePicker';
 import type { MediaFile } from '../types';
-import { formatDuration } from '../utils/mediaUtils';
+import { formatDuration, getTimelineDuration } from '../utils/mediaUtils';
 
 interface SortableItemProps {
   file: MediaFile;
   onRemove: (id: string) => void;
   onPreview: (file: MediaFile | null) => void;
+  onUpdate: (id: string, updates: Partial<MediaFile>) => void;
+  onSplit: (file: MediaFile) => void;
+  isSplitting: boolean;
+  isSelected: boolean;
+  onSelect: (id: string) => void;
 }
 
-function SortableItem({ file, onRemove, onPreview }: SortableItemProps) {
+function SortableItem({ file, onRemove, onPreview, onUpdate, onSplit, isSplitting, isSelected, onSelect }: SortableItemProps) {
   const {
     attributes,
     listeners,
@@ -89,10 +98,12 @@ function SortableItem({ file, onRemove, onPreview }: SortableItemProps) {
       onPointerDown={handlePointerDown}
       onPointerUp={handlePointerUp}
       onPointerLeave={handlePointerLeave}
+      onClick={() => onSelect(file.id)}
       className={`
         timeline-item relative flex-shrink-0 w-24 h-20 rounded-lg overflow-hidden
-        bg-gray-800 border border-gray-700 cursor-grab active:cursor-grabbing
+        bg-gray-800 border cursor-grab active:cursor-grabbing
         group select-none
+        ${isSelected ? 'border-indigo-500 ring-1 ring-indigo-500' : 'border-gray-700'}
       `}
     >
       {/* Thumbnail */}
@@ -127,13 +138,53 @@ function SortableItem({ file, onRemove, onPreview }: SortableItemProps) {
         </span>
       </div>
 
+      {/* Black bar removal - click to keep the bars */}
+      {file.contentRect && (
+        <button
+          onClick={(e) => {
+            e.stopPropagation();
+            onUpdate(file.id, { keepBlackBars: !file.keepBlackBars });
+          }}
+          onPointerDown={(e) => e.stopPropagation()}
+          title={file.keepBlackBars ? 'Black bars kept - click to crop them off' : 'Black bars cropped off - click to keep them'}
+          className={`
+            absolute top-6 left-1 px-1.5 py-0.5 text-[10px] font-medium rounded
+            ${file.keepBlackBars ? 'bg-gray-600/80 text-gray-300 line-through' : 'bg-amber-500/80 text-white'}
+          `}
+        >
+          BARS
+        </button>
+      )}
+
       {/* Duration */}
       <div className="absolute bottom-0 left-0 right-0 px-1.5 py-0.5 bg-black/70">
         <p className="text-[10px] text-white text-center font-medium">
-          {formatDuration(file.duration)}
+          {formatDuration(getTimelineDuration(file))}
         </p>
       </div>
 
+      {/* Split into scenes */}
+      {canSplitScenes(file) && (
+        <button
+          onClick={(e) => {
+            e.stopPropagation();
+            onSplit(file);
+          }}
+          onPointerDown={(e) => e.stopPropagation()}
+          disabled={isSplitting}
+          title="Split into scenes"
+          className={`
+            absolute top-1 right-7 w-5 h-5 rounded-full bg-gray-900/80
+            flex items-center justify-center transition-opacity hover:bg-gray-900
+            ${isSplitting ? 'opacity-100 animate-pulse' : 'opacity-0 group-hover:opacity-100'}
+          `}
+        >
+          <svg className="w-3 h-3 text-white" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 9a3 3 0 100-6 3 3 0 000 6zm0 12a3 3 0 100-6 3 3 0 000 6zM8.1 7.9L20 19M8.1 16.1L20 5" />
+          </svg>
+        </button>
+      )}
+
       {/* Remove button */}
       <button
         onClick={(e) => {
@@ -207,9 +258,61 @@ function VideoPreviewPopup({ file }: VideoPreviewPopupProps) {
   );
 }
 
+interface ToolErrorProps {
+  message: string;
+  onDismiss: () => void;
+}
+
+function ToolError({ message, onDismiss }: ToolErrorProps) {
+  return (
+    <div className="flex items-start justify-between gap-2 px-2 py-1.5 bg-amber-500/20 border-b border-amber-500/50">
+      <p className="text-xs text-amber-300">{message}</p>
+      <button
+        onClick={onDismiss}
+        className="text-amber-400 hover:text-amber-300 flex-shrink-0"
+      >
+        <svg className="w-3.5 h-3.5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 18L18 6M6 6l12 12" />
+        </svg>
+      </button>
+    </div>
+  );
+}
+
 export function MediaTimeline() {
-  const { mediaFiles, cover, reorderMediaFiles, removeMediaFile, clearMediaFiles } = useProjectStore();
+  const {
+    mediaFiles,
+    cover,
+    selectedMediaId,
+    reorderMediaFiles,
+    removeMediaFile,
+    clearMediaFiles,
+    updateMediaFile,
+    setSelectedMediaId,
+  } = useProjectStore();
   const totalDuration = useTotalDuration();
+  const {
+    isReframing,
+    error: reframeError,
+    reframeAll,
+    canReframe,
+    clearError: clearReframeError,
+  } = useReframe();
+  const {
+    splittingId,
+    error: scenesError,
+    review: sceneReview,
+    reviewScenes,
+    splitReviewed,
+    cancelReview,
+    clearError: clearScenesError,
+  } = useScenes();
+  const {
+    isProcessing: isRemovingSilences,
+    error: silencesError,
+    removeAllSilences,
+    clearError: clearSilencesError,
+  } = useSilences();
   const [previewFile, setPreviewFile] = useState<MediaFile | null>(null);
 
   const sensors = useSensors(
@@ -260,6 +363,26 @@ export function MediaTimeline() {
           <span className="text-xs text-gray-400">
             Total: {formatDuration(totalDuration)}
           </span>
+          {canReframe && (
+            <button
+              onClick={reframeAll}
+              disabled={isReframing}
+              title="Follow the subject when cropping clips to the selected format"
+              className="text-xs text-indigo-400 hover:text-indigo-300 transition-colors disabled:text-gray-500"
+            >
+              {isReframing ? 'Reframing...' : 'Auto reframe'}
+            </button>
+          )}
+          {mediaFiles.some(canRemoveSilences) && (
+            <button
+              onClick={() => removeAllSilences()}
+              disabled={isRemovingSilences}
+              title="Cut silences longer than a second out of every video"
+              className="text-xs text-indigo-400 hover:text-indigo-300 transition-colors disabled:text-gray-500"
+            >
+              {isRemovingSilences ? 'Cutting...' : 'Cut silences'}
+            </button>
+          )}
           {mediaFiles.length > 0 && (
             <button
               onClick={clearMediaFiles}
@@ -271,6 +394,17 @@ export function MediaTimeline() {
         </div>
       </div>
 
+      {/* Errors from the timeline tools */}
+      {reframeError && (
+        <ToolError message={`Auto reframe failed: ${reframeError}`} onDismiss={clearReframeError} />
+      )}
+      {scenesError && (
+        <ToolError message={`Split into scenes: ${scenesError}`} onDismiss={clearScenesError} />
+      )}
+      {silencesError && (
+        <ToolError message={`Cut silences: ${silencesError}`} onDismiss={clearSilencesError} />
+      )}
+
       {/* Timeline items */}
       <div className="flex-1 overflow-x-auto overflow-y-hidden p-3">
         <div className="flex items-center gap-2 min-w-max">
@@ -297,6 +431,11 @@ export function MediaTimeline() {
                     file={file}
                     onRemove={removeMediaFile}
                     onPreview={setPreviewFile}
+                    onUpdate={updateMediaFile}
+                    onSplit={reviewScenes}
+                    isSplitting={splittingId === file.id}
+                    isSelected={selectedMediaId === file.id}
+                    onSelect={setSelectedMediaId}
                   />
                 ))}
               </div>
@@ -307,6 +446,16 @@ export function MediaTimeline() {
 
       {/* Video Preview Popup */}
       {previewFile && <VideoPreviewPopup file={previewFile} />}
+
+      {/* Pick which scene changes to cut at */}
+      {sceneReview && (
+        <ScenePicker
+          key={sceneReview.file.id}
+          review={sceneReview}
+          onSplit={splitReviewed}
+          onCancel={cancelReview}
+        />
+      )}
     </div>
   );
 }
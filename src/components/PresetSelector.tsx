@@ -1,5 +1,5 @@
 import { useProjectStore } from '../stores/projectStore';
-import type { FormatPreset } from '../types';
+import type { FormatPreset, FrameRateConversion } from '../types';
 import { ALL_PRESETS } from '../utils/presets';
 
 interface PresetButtonProps {
@@ -28,8 +28,23 @@ function PresetButton({ preset, isSelected, onSelect }: PresetButtonProps) {
   );
 }
 
+const FPS_CONVERSION_OPTIONS: { value: FrameRateConversion; label: string; title: string }[] = [
+  { value: 'drop', label: 'Fast', title: 'Drop or repeat frames' },
+  { value: 'blend', label: 'Blend', title: 'Blend neighbouring frames' },
+  { value: 'interpolate', label: 'Smooth', title: 'Motion interpolation - smoothest pans, much slower export' },
+];
+
 export function PresetSelector() {
-  const { selectedPreset, setSelectedPreset } = useProjectStore();
+  const {
+    mediaFiles,
+    selectedPreset,
+    setSelectedPreset,
+    frameRateConversion,
+    setFrameRateConversion,
+    deinterlaceDoubleRate,
+    setDeinterlaceDoubleRate,
+  } = useProjectStore();
+  const hasInterlaced = mediaFiles.some((f) => f.isInterlaced);
 
   return (
     <div className="space-y-3">
@@ -47,6 +62,43 @@ export function PresetSelector() {
         ))}
       </div>
 
+      {/* Frame rate conversion for clips at another frame rate */}
+      <div className="space-y-2">
+        <h4 className="text-xs text-gray-400">Frame Rate Conversion</h4>
+        <div className="flex gap-1 p-1 bg-gray-800/50 rounded-lg border border-gray-700">
+          {FPS_CONVERSION_OPTIONS.map((option) => (
+            <button
+              key={option.value}
+              title={option.title}
+              onClick={() => setFrameRateConversion(option.value)}
+              className={`
+                flex-1 py-1.5 rounded-md text-xs font-medium transition-all
+                ${frameRateConversion === option.value
+                  ? 'bg-indigo-500/20 text-white'
+                  : 'text-gray-400 hover:text-white'
+                }
+              `}
+            >
+              {option.label}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      {/* Deinterlacing: one frame per field keeps motion smooth (50i -> 50p) */}
+      {hasInterlaced && (
+        <label className="flex items-center justify-between gap-2 cursor-pointer">
+          <span className="text-xs text-gray-400" title="Interlaced clips export at their field rate, e.g. 50i becomes 50p">
+            Double frame rate for interlaced clips
+          </span>
+          <input
+            type="checkbox"
+            checked={deinterlaceDoubleRate}
+            onChange={(e) => setDeinterlaceDoubleRate(e.target.checked)}
+            className="accent-indigo-500"
+          />
+        </label>
+      )}
     </div>
   );
 }
@@ -1,7 +1,9 @@
 import { useMemo, useRef, useEffect, useCallback } from 'react';
 import { convertFileSrc } from '@tauri-apps/api/core';
 import { useProjectStore, useTotalDuration } from '../stores/projectStore';
-import { formatDuration } from '../utils/mediaUtils';
+import { useProxies } from '../hooks/useProxies';
+import { useRenderedPreview } from '../hooks/useRenderedPreview';
+import { formatDuration, getSourceTime, getTimelineDuration } from '../utils/mediaUtils';
 import type { MediaFile } from '../types';
 
 // Binary search to find segment containing given time - O(log n) instead of O(n)
@@ -37,9 +39,12 @@ export function PreviewPanel() {
   const setPreviewTime = useProjectStore((state) => state.setPreviewTime);
   const isPlaying = useProjectStore((state) => state.isPlaying);
   const setIsPlaying = useProjectStore((state) => state.setIsPlaying);
+  const updateMediaFile = useProjectStore((state) => state.updateMediaFile);
 
   const totalDuration = useTotalDuration();
+  const proxyPaths = useProxies();
   const videoRefs = useRef<Map<string, HTMLVideoElement>>(new Map());
+  const renderedRef = useRef<HTMLVideoElement | null>(null);
   const coverTimerRef = useRef<number | null>(null);
   const lastUpdateRef = useRef(0);
 
@@ -55,8 +60,9 @@ export function PreviewPanel() {
     }
 
     for (const file of mediaFiles) {
-      result.push({ type: 'media', startTime: time, endTime: time + file.duration, item: file });
-      time += file.duration;
+      const duration = getTimelineDuration(file);
+      result.push({ type: 'media', startTime: time, endTime: time + duration, item: file });
+      time += duration;
     }
 
     return result;
@@ -68,6 +74,11 @@ export function PreviewPanel() {
     return index >= 0 ? segments[index] : null;
   }, [segments, previewTime]);
 
+  // Reversed and boomerang clips play from a rendered preview
+  const currentFile: MediaFile | null = currentSegment?.type === 'media' ? currentSegment.item : null;
+  const segmentOffset = currentSegment ? previewTime - currentSegment.startTime : 0;
+  const rendered = useRenderedPreview(currentFile, segmentOffset);
+
   // Get only video files for preloading
   const videoFiles = useMemo(() => {
     return mediaFiles.filter((f): f is MediaFile & { type: 'video' } => f.type === 'video');
@@ -105,6 +116,17 @@ export function PreviewPanel() {
     }
   }, [currentSegment, setPreviewTime, totalDuration, isPlaying]);
 
+  // A proxy swapped in for the source starts at 0 - put it back where the
+  // preview was
+  const handleVideoLoaded = useCallback((videoId: string) => {
+    const video = videoRefs.current.get(videoId);
+    if (!video || currentSegment?.type !== 'media' || currentSegment.item.id !== videoId) return;
+    video.currentTime = getSourceTime(currentSegment.item, previewTime - currentSegment.startTime);
+    if (isPlaying) {
+      video.play().catch(() => {});
+    }
+  }, [currentSegment, previewTime, isPlaying]);
+
   // Handle video ended - move to next segment
   const handleVideoEnded = useCallback(() => {
     if (!currentSegment) return;
@@ -120,6 +142,40 @@ export function PreviewPanel() {
     }
   }, [currentSegment, segments, setPreviewTime, setIsPlaying]);
 
+  // The rendered chunk plays the clip's timeline as-is - no mapping back to
+  // the source
+  const handleRenderedTimeUpdate = useCallback(() => {
+    const now = Date.now();
+    if (now - lastUpdateRef.current < 33) return;
+    lastUpdateRef.current = now;
+
+    const video = renderedRef.current;
+    if (video && rendered && currentSegment && isPlaying) {
+      const newTime = currentSegment.startTime + rendered.start + video.currentTime;
+      setPreviewTime(Math.min(newTime, totalDuration));
+    }
+  }, [rendered, currentSegment, setPreviewTime, totalDuration, isPlaying]);
+
+  const handleRenderedLoaded = useCallback(() => {
+    const video = renderedRef.current;
+    if (!video || !rendered) return;
+    video.currentTime = Math.max(0, segmentOffset - rendered.start);
+    if (isPlaying) {
+      video.play().catch(() => {});
+    }
+  }, [rendered, segmentOffset, isPlaying]);
+
+  // On to the next chunk, or the next segment after the last one
+  const handleRenderedEnded = useCallback(() => {
+    if (!rendered || !currentSegment) return;
+    const chunkEnd = currentSegment.startTime + rendered.start + rendered.duration;
+    if (chunkEnd >= currentSegment.endTime - 0.01) {
+      handleVideoEnded();
+    } else {
+      setPreviewTime(chunkEnd);
+    }
+  }, [rendered, currentSegment, handleVideoEnded, setPreviewTime]);
+
   // Handle cover timer during playback
   useEffect(() => {
     if (isPlaying && currentSegment?.type === 'cover') {
@@ -170,15 +226,19 @@ export function PreviewPanel() {
     const video = videoRefs.current.get(currentVideoId);
     if (!video) return;
 
-    const videoTime = previewTime - currentSegment.startTime;
+    const videoTime = getSourceTime(currentSegment.item, previewTime - currentSegment.startTime);
+    video.playbackRate = currentSegment.item.speed ?? 1;
+    video.muted = currentSegment.item.muteAudio ?? false;
 
     // Seek if needed (threshold reduced for smoother scrubbing)
     if (Math.abs(video.currentTime - videoTime) > 0.1) {
       video.currentTime = Math.max(0, Math.min(videoTime, video.duration || Infinity));
     }
 
-    // Play/pause based on state
-    if (isPlaying && video.paused) {
+    // Play/pause based on state; a rendered preview plays in its place
+    if (rendered) {
+      if (!video.paused) video.pause();
+    } else if (isPlaying && video.paused) {
       video.play().catch(() => {});
     } else if (!isPlaying && !video.paused) {
       video.pause();
@@ -190,7 +250,24 @@ export function PreviewPanel() {
         v.pause();
       }
     });
-  }, [isPlaying, currentSegment, previewTime]);
+  }, [isPlaying, currentSegment, previewTime, rendered]);
+
+  // Keep the rendered preview in step with the timeline
+  useEffect(() => {
+    const video = renderedRef.current;
+    if (!video || !rendered?.path) return;
+
+    const videoTime = segmentOffset - rendered.start;
+    if (Math.abs(video.currentTime - videoTime) > 0.1) {
+      video.currentTime = Math.max(0, Math.min(videoTime, video.duration || Infinity));
+    }
+
+    if (isPlaying && video.paused) {
+      video.play().catch(() => {});
+    } else if (!isPlaying && !video.paused) {
+      video.pause();
+    }
+  }, [isPlaying, rendered, segmentOffset]);
 
   // Register video ref
   const setVideoRef = useCallback((id: string, el: HTMLVideoElement | null) => {
@@ -237,10 +314,22 @@ export function PreviewPanel() {
     }
   }, [segments, setPreviewTime, setIsPlaying]);
 
+  // Pin the current video's thumbnail to the frame on screen
+  const setPosterFrame = useCallback(() => {
+    if (currentSegment?.type !== 'media') return;
+    const file: MediaFile = currentSegment.item;
+    const posterTime = getSourceTime(file, previewTime - currentSegment.startTime);
+    updateMediaFile(file.id, { posterTime, thumbnail: undefined });
+  }, [currentSegment, previewTime, updateMediaFile]);
+
   const hasContent = mediaFiles.length > 0 || cover.text.trim().length > 0;
   const showCover = cover.text.trim().length > 0;
   const currentItemId = currentSegment?.type === 'media' ? currentSegment.item.id : null;
   const showCoverPreview = currentSegment?.type === 'cover';
+  const canSetPoster = currentSegment?.type === 'media'
+    && currentSegment.item.type === 'video'
+    && !currentSegment.item.isAnimated
+    && !currentSegment.item.imageSequence;
 
   return (
     <div className="h-full flex flex-col">
@@ -289,10 +378,11 @@ export function PreviewPanel() {
                 <video
                   key={file.id}
                   ref={(el) => setVideoRef(file.id, el)}
-                  src={getFileSrc(file.path)}
+                  src={getFileSrc(proxyPaths[file.path] ?? file.path)}
                   className={`absolute inset-0 w-full h-full object-contain transition-opacity duration-75 ${
                     currentItemId === file.id && !showCoverPreview ? 'opacity-100 z-5' : 'opacity-0 pointer-events-none'
                   }`}
+                  onLoadedMetadata={() => handleVideoLoaded(file.id)}
                   onTimeUpdate={() => handleVideoTimeUpdate(file.id)}
                   onEnded={handleVideoEnded}
                   preload="auto"
@@ -301,6 +391,21 @@ export function PreviewPanel() {
                 />
               ))}
 
+              {/* Rendered reverse/boomerang preview, over the paused source */}
+              {rendered?.path && !showCoverPreview && (
+                <video
+                  key={rendered.path}
+                  ref={renderedRef}
+                  src={getFileSrc(rendered.path)}
+                  className="absolute inset-0 w-full h-full object-contain z-5"
+                  onLoadedMetadata={handleRenderedLoaded}
+                  onTimeUpdate={handleRenderedTimeUpdate}
+                  onEnded={handleRenderedEnded}
+                  preload="auto"
+                  playsInline
+                />
+              )}
+
               {/* Images - only render current one */}
               {imageFiles.map((file) => (
                 <img
@@ -315,6 +420,23 @@ export function PreviewPanel() {
             </>
           )}
 
+          {/* Poster frame */}
+          {canSetPoster && !isPlaying && (
+            <button
+              onClick={setPosterFrame}
+              title="Use this frame as the clip's thumbnail"
+              className="absolute top-2 left-2 px-2 py-1 bg-black/60 hover:bg-black/80 rounded text-[10px] text-white z-20 transition-colors"
+            >
+              Set as poster
+            </button>
+          )}
+
+          {rendered && !rendered.path && (
+            <div className="absolute bottom-2 left-2 px-2 py-1 bg-black/60 rounded text-[10px] text-white z-20">
+              Rendering preview…
+            </div>
+          )}
+
           {/* Preset overlay */}
           {selectedPreset && (
             <div className="absolute top-2 right-2 px-2 py-1 bg-black/60 rounded text-[10px] text-white z-20">
@@ -45,6 +45,7 @@ export function ProgressModal({ outputPath, onClose }: ProgressModalProps) {
   const progress = exportProgress?.progress || 0;
   const currentFile = exportProgress?.currentFile;
   const error = exportProgress?.error;
+  const warning = exportProgress?.warning;
 
   return (
     <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/60 backdrop-blur-sm">
@@ -76,6 +77,9 @@ export function ProgressModal({ outputPath, onClose }: ProgressModalProps) {
                   {outputPath.split('/').pop()}
                 </p>
               )}
+              {warning && (
+                <p className="text-xs text-yellow-400 px-4">{warning}</p>
+              )}
             </div>
           ) : error ? (
             <div className="text-center space-y-4">
@@ -0,0 +1,111 @@
+import { useState } from 'react';
+import type { SceneReview } from '../hooks/useScenes';
+import { formatDuration } from '../utils/mediaUtils';
+
+interface ScenePickerProps {
+  review: SceneReview;
+  onSplit: (cutTimes: number[]) => void;
+  onCancel: () => void;
+}
+
+/**
+ * Scene changes found in a clip, each with the first frame of the new shot
+ * and how hard the cut is. The clip is only split at the ones left ticked.
+ */
+export function ScenePicker({ review, onSplit, onCancel }: ScenePickerProps) {
+  const { file, boundaries } = review;
+  const [selected, setSelected] = useState<Set<number>>(() => new Set(boundaries.map((_, i) => i)));
+  const start = file.trimStart ?? 0;
+
+  const toggle = (index: number) => {
+    setSelected((current) => {
+      const next = new Set(current);
+      if (next.has(index)) {
+        next.delete(index);
+      } else {
+        next.add(index);
+      }
+      return next;
+    });
+  };
+
+  const allSelected = selected.size === boundaries.length;
+  const cutTimes = boundaries.filter((_, i) => selected.has(i)).map((b) => b.time);
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/60 backdrop-blur-sm">
+      <div className="w-full max-w-lg mx-4 bg-gray-900 rounded-2xl border border-gray-800 shadow-2xl overflow-hidden">
+        {/* Header */}
+        <div className="px-6 py-4 border-b border-gray-800 flex items-center justify-between gap-3">
+          <div className="min-w-0">
+            <h2 className="text-lg font-semibold text-white">Split into scenes</h2>
+            <p className="text-xs text-gray-400 truncate">{file.name}</p>
+          </div>
+          <button
+            onClick={() => setSelected(allSelected ? new Set() : new Set(boundaries.map((_, i) => i)))}
+            className="text-xs text-indigo-400 hover:text-indigo-300 transition-colors flex-shrink-0"
+          >
+            {allSelected ? 'Select none' : 'Select all'}
+          </button>
+        </div>
+
+        {/* Boundaries */}
+        <div className="px-6 py-4 max-h-[60vh] overflow-y-auto space-y-2">
+          {boundaries.map((boundary, i) => (
+            <label
+              key={boundary.time}
+              className={`flex items-center gap-3 p-2 rounded-lg border cursor-pointer transition-colors ${
+                selected.has(i) ? 'border-indigo-500/50 bg-indigo-500/10' : 'border-gray-800 hover:border-gray-700'
+              }`}
+            >
+              <input
+                type="checkbox"
+                checked={selected.has(i)}
+                onChange={() => toggle(i)}
+                className="accent-indigo-500"
+              />
+              {boundary.thumbnail ? (
+                <img
+                  src={boundary.thumbnail}
+                  alt=""
+                  className="w-20 h-12 object-cover rounded bg-gray-800 flex-shrink-0"
+                />
+              ) : (
+                <div className="w-20 h-12 rounded bg-gray-800 flex-shrink-0" />
+              )}
+              <div className="flex-1 min-w-0">
+                <p className="text-sm text-white font-mono">{formatDuration(boundary.time - start)}</p>
+                <div className="mt-1 h-1 bg-gray-800 rounded-full overflow-hidden" title="How hard the cut is">
+                  <div
+                    className="h-full bg-indigo-500 rounded-full"
+                    style={{ width: `${Math.round(boundary.score * 100)}%` }}
+                  />
+                </div>
+              </div>
+              <span className="text-xs text-gray-400 font-mono w-10 text-right">
+                {Math.round(boundary.score * 100)}%
+              </span>
+            </label>
+          ))}
+        </div>
+
+        {/* Actions */}
+        <div className="px-6 py-4 border-t border-gray-800 flex gap-3">
+          <button
+            onClick={onCancel}
+            className="flex-1 py-2.5 px-4 bg-gray-800 hover:bg-gray-700 text-white rounded-lg font-medium text-sm transition-colors"
+          >
+            Cancel
+          </button>
+          <button
+            onClick={() => onSplit(cutTimes)}
+            disabled={cutTimes.length === 0}
+            className="flex-1 py-2.5 px-4 bg-indigo-500 hover:bg-indigo-600 text-white rounded-lg font-medium text-sm transition-colors disabled:opacity-40"
+          >
+            Split into {cutTimes.length + 1} clips
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -6,37 +6,15 @@ import { save } from '@tauri-apps/plugin-dialog';
 import { downloadDir } from '@tauri-apps/api/path';
 import { useProjectStore } from '../stores/projectStore';
 import type { ExportProgress } from '../types';
-
-interface ExportConfig {
-  preset_id: string;
-  width: number;
-  height: number;
-  codec: string;
-  framerate: number | null;
-  bitrate: number | null;
-}
-
-interface MediaItem {
-  path: string;
-  media_type: string;
-  duration: number;
-  width: number | null;
-  height: number | null;
-  framerate: number | null;
-}
-
-interface CoverConfig {
-  enabled: boolean;
-  text: string;
-  duration: number;
-  color_scheme: string;
-}
+import { toCoverConfig, toExportConfig, toMediaItem } from '../utils/exportPayload';
 
 export function useExport() {
   const {
     mediaFiles,
     cover,
     selectedPreset,
+    frameRateConversion,
+    deinterlaceDoubleRate,
     isExporting,
     exportProgress,
     setIsExporting,
@@ -113,36 +91,14 @@ export function useExport() {
         progress: 0,
       });
 
-      const framerate = firstVideo?.framerate ?? null;
-      const bitrate = firstVideo?.bitrate ?? null;
-
       // Prepare export config
-      const config: ExportConfig = {
-        preset_id: selectedPreset.id,
-        width: selectedPreset.width,
-        height: selectedPreset.height,
-        codec: selectedPreset.codec,
-        framerate,
-        bitrate,
-      };
+      const config = toExportConfig(selectedPreset, mediaFiles, frameRateConversion, deinterlaceDoubleRate);
 
       // Prepare media items
-      const mediaItems: MediaItem[] = mediaFiles.map((file) => ({
-        path: file.path,
-        media_type: file.type,
-        duration: file.duration,
-        width: file.width ?? null,
-        height: file.height ?? null,
-        framerate: file.framerate ?? null,
-      }));
+      const mediaItems = mediaFiles.map(toMediaItem);
 
       // Prepare cover config - enabled when text is not empty
-      const coverConfig: CoverConfig = {
-        enabled: cover.text.trim().length > 0,
-        text: cover.text,
-        duration: cover.duration,
-        color_scheme: cover.colorScheme,
-      };
+      const coverConfig = toCoverConfig(cover);
 
       // Start export
       const result: string = await invoke('export_video', {
@@ -173,6 +129,8 @@ export function useExport() {
     mediaFiles,
     cover,
     selectedPreset,
+    frameRateConversion,
+    deinterlaceDoubleRate,
     setIsExporting,
     setExportProgress,
   ]);
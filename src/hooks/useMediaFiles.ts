@@ -1,26 +1,74 @@
 import { useState, useCallback, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { ask } from '@tauri-apps/plugin-dialog';
 import { v4 as uuidv4 } from 'uuid';
-import type { MediaFile } from '../types';
+import type { CropRect, LivePhotoMode, MediaFile } from '../types';
 import { useProjectStore } from '../stores/projectStore';
-import { getMediaType, DEFAULT_IMAGE_DURATION } from '../utils/mediaUtils';
+import { canProxy } from './useProxies';
+import { DEFAULT_IMAGE_DURATION } from '../utils/mediaUtils';
 
 interface MediaMetadata {
   path: string;
   name: string;
   media_type: string;
+  is_animated: boolean;
   duration: number;
-  width: number;
+  width: number; // as displayed, after EXIF orientation
   height: number;
+  orientation: number; // EXIF orientation 1-8
   timestamp: number;
   thumbnail: string | null;
   framerate: number | null;
   bitrate: number | null;
+  is_vfr: boolean | null; // null until export checks the frame timing
+  is_interlaced: boolean;
+  has_audio: boolean;
+  frame_count: number | null;
+  image_sequence: { start_number: number; frame_count: number; framerate: number } | null;
+  live_photo: { motion_path: string; motion_duration: number; mode: LivePhotoMode } | null;
+  content_rect: CropRect | null; // picture inside burned-in black bars
+}
+
+interface LivePhoto {
+  still_path: string;
+  motion_path: string;
+  motion_duration: number;
+  content_identifier: string | null;
+}
+
+interface LivePhotoScan {
+  live_photos: LivePhoto[];
+  other_paths: string[];
+}
+
+interface ImageSequence {
+  pattern: string;
+  name: string;
+  first_path: string;
+  start_number: number;
+  frame_count: number;
+  paths: string[];
+}
+
+interface SequenceScan {
+  sequences: ImageSequence[];
+  other_paths: string[];
+}
+
+export interface RejectedFile {
+  path: string;
+  reason: string; // e.g. "Unsupported media type: audio-only file"
+}
+
+interface MetadataBatch {
+  items: MediaMetadata[];
+  rejected: RejectedFile[];
 }
 
 export function useMediaFiles() {
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [rejected, setRejected] = useState<RejectedFile[]>([]);
   const addMediaFiles = useProjectStore((state) => state.addMediaFiles);
   const updateMediaFile = useProjectStore((state) => state.updateMediaFile);
   const mediaFiles = useProjectStore((state) => state.mediaFiles);
@@ -33,16 +81,22 @@ export function useMediaFiles() {
       if (filesNeedingThumbs.length === 0) return;
 
       // Prepare batch request
-      const items: [string, string][] = filesNeedingThumbs.map((f) => [f.path, f.type]);
+      const items: [string, string, number | null][] = filesNeedingThumbs.map((f) => [
+        f.path,
+        f.type,
+        f.posterTime ?? null,
+      ]);
 
       try {
-        const results: [string, string | null][] = await invoke('generate_thumbnails_batch', { items });
+        const results: [string, number | null, string | null][] = await invoke('generate_thumbnails_batch', { items });
 
         // Update files with thumbnails
-        for (const [path, thumbnail] of results) {
+        for (const [path, posterTime, thumbnail] of results) {
           if (thumbnail) {
-            const file = mediaFiles.find((f) => f.path === path);
-            if (file && updateMediaFile) {
+            const files = filesNeedingThumbs.filter(
+              (f) => f.path === path && (f.posterTime ?? null) === posterTime
+            );
+            for (const file of files) {
               updateMediaFile(file.id, { thumbnail });
             }
           }
@@ -60,10 +114,46 @@ export function useMediaFiles() {
   const processFiles = useCallback(async (paths: string[]) => {
     setIsLoading(true);
     setError(null);
+    setRejected([]);
 
     try {
-      // Get metadata for all files (parallel, no thumbnails - fast!)
-      const metadataList: MediaMetadata[] = await invoke('get_media_metadata_batch', { paths });
+      // Live Photos (still + MOV) become one item each
+      const liveScan: LivePhotoScan = await invoke('find_live_photos', { paths });
+      const liveMetadata = await Promise.all(
+        liveScan.live_photos.map((livePhoto) =>
+          invoke<MediaMetadata>('get_live_photo_metadata', { livePhoto })
+        )
+      );
+
+      // Numbered stills (timelapse frames) can become one clip per
+      // sequence - but a run of ordinary camera files looks the same, so ask
+      let scan: SequenceScan = await invoke('find_image_sequences', { paths: liveScan.other_paths });
+      if (scan.sequences.length > 0) {
+        const frameCount = scan.sequences.reduce((sum, seq) => sum + seq.frame_count, 0);
+        const asTimelapse = await ask(
+          `${frameCount} numbered stills look like ${scan.sequences.length === 1 ? 'a timelapse' : `${scan.sequences.length} timelapses`} `
+            + `(${scan.sequences.map((seq) => seq.name).join(', ')}). Import them as timelapse clips?`,
+          { title: 'Image sequence found', okLabel: 'Timelapse', cancelLabel: 'Separate photos' }
+        );
+        if (!asTimelapse) {
+          scan = {
+            sequences: [],
+            other_paths: [...scan.other_paths, ...scan.sequences.flatMap((seq) => seq.paths)],
+          };
+        }
+      }
+      const sequenceMetadata = await Promise.all(
+        scan.sequences.map((sequence) =>
+          invoke<MediaMetadata>('get_image_sequence_metadata', { sequence, framerate: null })
+        )
+      );
+
+      // Get metadata for all other files (parallel, no thumbnails - fast!).
+      // The backend sniffs each file's contents, so extensions don't matter.
+      const batch: MetadataBatch = await invoke('get_media_metadata_batch', { paths: scan.other_paths });
+      setRejected(batch.rejected);
+      const metadataList = [...liveMetadata, ...sequenceMetadata, ...batch.items]
+        .sort((a, b) => a.timestamp - b.timestamp);
 
       // Convert to MediaFile format (no thumbnails yet)
       const mediaFilesNew: MediaFile[] = metadataList.map((meta) => ({
@@ -78,9 +168,54 @@ export function useMediaFiles() {
         height: meta.height,
         framerate: meta.framerate || undefined,
         bitrate: meta.bitrate || undefined,
+        isVfr: meta.is_vfr ?? undefined,
+        isInterlaced: meta.is_interlaced || undefined,
+        hasAudio: meta.has_audio,
+        isAnimated: meta.is_animated || undefined,
+        loopDuration: meta.is_animated ? meta.duration : undefined,
+        imageSequence: meta.image_sequence
+          ? {
+              startNumber: meta.image_sequence.start_number,
+              frameCount: meta.image_sequence.frame_count,
+              framerate: meta.image_sequence.framerate,
+            }
+          : undefined,
+        livePhoto: meta.live_photo
+          ? {
+              motionPath: meta.live_photo.motion_path,
+              motionDuration: meta.live_photo.motion_duration,
+              mode: meta.live_photo.mode,
+            }
+          : undefined,
+        contentRect: meta.content_rect ?? undefined,
       }));
 
       addMediaFiles(mediaFilesNew);
+
+      // Transcode heavy videos into preview proxies in the background
+      const proxyPaths = [...new Set(mediaFilesNew.filter(canProxy).map((f) => f.path))];
+      if (proxyPaths.length > 0) {
+        invoke('request_proxies', { paths: proxyPaths })
+          .catch((err) => console.error('Failed to request proxies:', err));
+      }
+
+      // Look for burned-in black bars in the background, one clip at a time
+      const plainVideos = mediaFilesNew.filter(
+        (f) => f.type === 'video' && !f.isAnimated && !f.imageSequence && !f.contentRect
+      );
+      void (async () => {
+        for (const file of plainVideos) {
+          try {
+            const contentRect = await invoke<CropRect | null>('detect_black_bars', { path: file.path });
+            if (contentRect) {
+              updateMediaFile(file.id, { contentRect });
+            }
+          } catch (err) {
+            console.error('Failed to detect black bars:', err);
+          }
+        }
+      })();
+
       return mediaFilesNew;
     } catch (err) {
       const errorMessage = err instanceof Error ? err.message : 'Failed to process files';
@@ -89,33 +224,31 @@ export function useMediaFiles() {
     } finally {
       setIsLoading(false);
     }
-  }, [addMediaFiles]);
+  }, [addMediaFiles, updateMediaFile]);
 
   const processDroppedFiles = useCallback(async (files: FileList | File[]) => {
-    const fileArray = Array.from(files);
-    const validPaths: string[] = [];
-
-    for (const file of fileArray) {
-      const mediaType = getMediaType(file.name);
-      if (mediaType) {
-        const path = (file as unknown as { path?: string }).path || file.name;
-        validPaths.push(path);
-      }
-    }
+    // Every file goes to the backend, which decides what it can import
+    const paths = Array.from(files).map(
+      (file) => (file as unknown as { path?: string }).path || file.name
+    );
 
-    if (validPaths.length === 0) {
+    if (paths.length === 0) {
       setError('No valid media files found');
       return [];
     }
 
-    return processFiles(validPaths);
+    return processFiles(paths);
   }, [processFiles]);
 
   return {
     isLoading,
     error,
+    rejected,
     processFiles,
     processDroppedFiles,
-    clearError: () => setError(null),
+    clearError: () => {
+      setError(null);
+      setRejected([]);
+    },
   };
 }
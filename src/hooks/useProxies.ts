@@ -0,0 +1,109 @@
+import { useState, useEffect, useMemo, useRef, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import type { MediaFile } from '../types';
+import { useProjectStore } from '../stores/projectStore';
+
+export interface ProxyEntry {
+  path: string;
+  status: 'not_needed' | 'queued' | 'generating' | 'ready' | 'failed';
+  proxy_path: string | null;
+  error: string | null;
+}
+
+/**
+ * Plain videos get proxies; animations and sequences play from stills
+ */
+export const canProxy = (file: MediaFile): boolean => {
+  return file.type === 'video' && !file.isAnimated && !file.imageSequence;
+};
+
+/**
+ * Preview proxies for the timeline's videos: source path -> proxy path for
+ * every proxy that's ready. Proxies of files taken off the timeline are
+ * deleted.
+ */
+export function useProxies() {
+  const mediaFiles = useProjectStore((state) => state.mediaFiles);
+  const [proxyPaths, setProxyPaths] = useState<Record<string, string>>({});
+  const previousPathsRef = useRef<string[]>([]);
+
+  // Joined so the effects below only rerun when the set of videos changes
+  const pathsKey = useMemo(
+    () => [...new Set(mediaFiles.filter(canProxy).map((f) => f.path))].sort().join('\n'),
+    [mediaFiles]
+  );
+
+  const applyEntries = useCallback((entries: ProxyEntry[]) => {
+    setProxyPaths((current) => {
+      const next = { ...current };
+      for (const entry of entries) {
+        if (entry.status === 'ready' && entry.proxy_path) {
+          next[entry.path] = entry.proxy_path;
+        } else {
+          delete next[entry.path];
+        }
+      }
+      return next;
+    });
+  }, []);
+
+  // Proxies finishing in the background
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    let mounted = true;
+
+    const setupListener = async () => {
+      try {
+        const unlistenFn = await listen<ProxyEntry>('proxy-status', (event) => {
+          if (mounted) {
+            applyEntries([event.payload]);
+          }
+        });
+        if (mounted) {
+          unlisten = unlistenFn;
+        } else {
+          unlistenFn();
+        }
+      } catch (err) {
+        console.error('Failed to set up proxy status listener:', err);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      mounted = false;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [applyEntries]);
+
+  // Catch up on proxies that finished before we were listening, and clean
+  // up after files that were removed or cleared
+  useEffect(() => {
+    const paths = pathsKey ? pathsKey.split('\n') : [];
+    const removed = previousPathsRef.current.filter((p) => !paths.includes(p));
+    previousPathsRef.current = paths;
+
+    if (paths.length > 0) {
+      invoke<ProxyEntry[]>('get_proxy_status', { paths })
+        .then(applyEntries)
+        .catch((err) => console.error('Failed to get proxy status:', err));
+    }
+
+    if (removed.length > 0) {
+      setProxyPaths((current) => {
+        const next = { ...current };
+        for (const path of removed) delete next[path];
+        return next;
+      });
+      invoke<number>('cleanup_proxies', { keepPaths: paths })
+        .catch((err) => console.error('Failed to clean up proxies:', err));
+    }
+  }, [pathsKey, applyEntries]);
+
+  return proxyPaths;
+}
@@ -0,0 +1,72 @@
+import { useState, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { CropKeyframe, MediaFile } from '../types';
+import { useProjectStore } from '../stores/projectStore';
+
+interface ReframePath {
+  crop_keyframes: CropKeyframe[];
+  shot_count: number;
+}
+
+/**
+ * Clips that don't already match the preset's shape and can be analysed:
+ * plain videos with known dimensions
+ */
+const needsReframe = (file: MediaFile, width: number, height: number): boolean => {
+  if (file.type !== 'video' || file.isAnimated || file.imageSequence) return false;
+  if (!file.width || !file.height) return false;
+  return Math.abs(file.width / file.height / (width / height) - 1) >= 0.01;
+};
+
+export function useReframe() {
+  const { mediaFiles, selectedPreset, updateMediaFile } = useProjectStore();
+  const [isReframing, setIsReframing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  /**
+   * Follow the subject of every clip whose shape differs from the preset,
+   * replacing its crop with the analysed path
+   */
+  const reframeAll = useCallback(async () => {
+    if (!selectedPreset) return;
+    const targets = mediaFiles.filter((f) => needsReframe(f, selectedPreset.width, selectedPreset.height));
+    if (targets.length === 0) return;
+
+    setIsReframing(true);
+    setError(null);
+    try {
+      // One at a time - each pass decodes the whole clip
+      for (const file of targets) {
+        const path = await invoke<ReframePath>('analyze_reframe', {
+          path: file.path,
+          width: file.width,
+          height: file.height,
+          targetWidth: selectedPreset.width,
+          targetHeight: selectedPreset.height,
+        });
+        updateMediaFile(file.id, {
+          transform: {
+            ...file.transform,
+            crop: undefined,
+            cropKeyframes: path.crop_keyframes,
+          },
+        });
+      }
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsReframing(false);
+    }
+  }, [mediaFiles, selectedPreset, updateMediaFile]);
+
+  const canReframe = selectedPreset !== null
+    && mediaFiles.some((f) => needsReframe(f, selectedPreset.width, selectedPreset.height));
+
+  return {
+    isReframing,
+    error,
+    reframeAll,
+    canReframe,
+    clearError: () => setError(null),
+  };
+}
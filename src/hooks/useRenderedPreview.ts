@@ -0,0 +1,76 @@
+import { useState, useEffect, useMemo, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { MediaFile } from '../types';
+import { useProjectStore } from '../stores/projectStore';
+import { canProxy } from './useProxies';
+import { getTimelineDuration } from '../utils/mediaUtils';
+import { toCoverConfig, toExportConfig, toMediaItem } from '../utils/exportPayload';
+
+// Longest clip render_preview_clip renders at once
+const RENDERED_CHUNK_DURATION = 15;
+
+/**
+ * Reversed and boomerang videos can't be previewed by seeking the source
+ * while it plays forward - they're rendered instead
+ */
+export const needsRenderedPreview = (file: MediaFile): boolean => {
+  return canProxy(file) && (file.playback === 'reverse' || file.playback === 'boomerang');
+};
+
+export interface RenderedChunk {
+  path: string | null; // null while rendering
+  start: number; // seconds into the clip's timeline slot
+  duration: number;
+}
+
+/**
+ * The rendered chunk of `file` covering `offset` seconds into its timeline
+ * slot, or null when the file plays from its source
+ */
+export function useRenderedPreview(file: MediaFile | null, offset: number): RenderedChunk | null {
+  const cover = useProjectStore((state) => state.cover);
+  const selectedPreset = useProjectStore((state) => state.selectedPreset);
+  const frameRateConversion = useProjectStore((state) => state.frameRateConversion);
+  const deinterlaceDoubleRate = useProjectStore((state) => state.deinterlaceDoubleRate);
+  // Rendered chunk paths; null for chunks that failed to render
+  const [chunkPaths, setChunkPaths] = useState<Record<string, string | null>>({});
+  const pendingRef = useRef<Set<string>>(new Set());
+
+  const request = useMemo(() => {
+    if (!file || !selectedPreset || !needsRenderedPreview(file)) return null;
+    const total = getTimelineDuration(file);
+    const lastChunk = Math.max(Math.ceil(total / RENDERED_CHUNK_DURATION) - 1, 0);
+    const start = Math.min(Math.floor(Math.max(offset, 0) / RENDERED_CHUNK_DURATION), lastChunk)
+      * RENDERED_CHUNK_DURATION;
+    const payload = {
+      mediaItems: [toMediaItem(file)],
+      cover: toCoverConfig({ ...cover, text: '' }),
+      config: toExportConfig(selectedPreset, [file], frameRateConversion, deinterlaceDoubleRate),
+      start,
+      duration: Math.min(RENDERED_CHUNK_DURATION, total - start),
+    };
+    return { key: JSON.stringify(payload), payload };
+  }, [file, offset, cover, selectedPreset, frameRateConversion, deinterlaceDoubleRate]);
+
+  useEffect(() => {
+    if (!request || request.key in chunkPaths || pendingRef.current.has(request.key)) return;
+    const { key, payload } = request;
+
+    pendingRef.current.add(key);
+    invoke<string>('render_preview_clip', payload)
+      .then((path) => setChunkPaths((current) => ({ ...current, [key]: path })))
+      .catch((err) => {
+        console.error('Failed to render preview:', err);
+        setChunkPaths((current) => ({ ...current, [key]: null }));
+      })
+      .finally(() => pendingRef.current.delete(key));
+  }, [request, chunkPaths]);
+
+  // Without a render the source plays as best it can
+  if (!request || chunkPaths[request.key] === null) return null;
+  return {
+    path: chunkPaths[request.key] ?? null,
+    start: request.payload.start,
+    duration: request.payload.duration,
+  };
+}
@@ -0,0 +1,140 @@
+import { useState, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { v4 as uuidv4 } from 'uuid';
+import type { MediaFile } from '../types';
+import { useProjectStore } from '../stores/projectStore';
+
+export interface SceneBoundary {
+  time: number; // seconds into the source
+  score: number; // 0-1, higher is a harder cut
+  thumbnail: string | null;
+}
+
+interface SceneDetection {
+  duration: number;
+  boundaries: SceneBoundary[];
+}
+
+/**
+ * Boundaries found in a file, waiting for the user to pick where to cut
+ */
+export interface SceneReview {
+  file: MediaFile;
+  boundaries: SceneBoundary[];
+}
+
+interface SceneClip {
+  trim_start: number;
+  duration: number;
+  thumbnail: string | null;
+}
+
+/**
+ * Plain videos can be split; animations, sequences and Live Photos can't
+ */
+export const canSplitScenes = (file: MediaFile): boolean => {
+  return file.type === 'video' && !file.isAnimated && !file.imageSequence;
+};
+
+export function useScenes() {
+  const replaceMediaFile = useProjectStore((state) => state.replaceMediaFile);
+  const [splittingId, setSplittingId] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [review, setReview] = useState<SceneReview | null>(null);
+
+  /**
+   * Shot boundaries inside the part of the source a file plays
+   */
+  const detectScenes = useCallback(async (file: MediaFile, sensitivity?: number) => {
+    const detection = await invoke<SceneDetection>('detect_scenes', {
+      path: file.path,
+      sensitivity: sensitivity ?? null,
+    });
+    const start = file.trimStart ?? 0;
+    return detection.boundaries.filter((b) => b.time > start && b.time < start + file.duration);
+  }, []);
+
+  /**
+   * Replace a file with trimmed copies, one per piece between `cutTimes`.
+   * Each piece keeps the file's settings; nothing is re-encoded.
+   */
+  const splitAtScenes = useCallback(async (file: MediaFile, cutTimes: number[]) => {
+    const clips = await invoke<SceneClip[]>('split_at_scenes', {
+      path: file.path,
+      trimStart: file.trimStart ?? null,
+      duration: file.duration,
+      cutTimes,
+    });
+    if (clips.length < 2) return [file];
+
+    const start = file.trimStart ?? 0;
+    const pieces: MediaFile[] = clips.map((clip, i) => ({
+      ...file,
+      id: uuidv4(),
+      name: `${file.name} (${i + 1})`,
+      trimStart: clip.trim_start,
+      duration: clip.duration,
+      // Keeps the pieces in order when the timeline is re-sorted
+      timestamp: file.timestamp + (clip.trim_start - start) * 1000,
+      thumbnail: clip.thumbnail ?? file.thumbnail,
+    }));
+    replaceMediaFile(file.id, pieces);
+    return pieces;
+  }, [replaceMediaFile]);
+
+  /**
+   * Detect scenes and hold the boundaries for review instead of cutting
+   * at all of them
+   */
+  const reviewScenes = useCallback(async (file: MediaFile, sensitivity?: number) => {
+    setSplittingId(file.id);
+    setError(null);
+    try {
+      const boundaries = await detectScenes(file, sensitivity);
+      if (boundaries.length === 0) {
+        setError('No scene changes found');
+        return;
+      }
+      setReview({ file, boundaries });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSplittingId(null);
+    }
+  }, [detectScenes]);
+
+  /**
+   * Split the reviewed file at the boundaries the user kept
+   */
+  const splitReviewed = useCallback(async (cutTimes: number[]) => {
+    if (!review) return [];
+    setReview(null);
+
+    // The file may have changed or gone while the review was open
+    const file = useProjectStore.getState().mediaFiles.find((f) => f.id === review.file.id);
+    if (!file || cutTimes.length === 0) return file ? [file] : [];
+
+    setSplittingId(file.id);
+    setError(null);
+    try {
+      return await splitAtScenes(file, cutTimes);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+      return [file];
+    } finally {
+      setSplittingId(null);
+    }
+  }, [review, splitAtScenes]);
+
+  return {
+    splittingId,
+    error,
+    review,
+    detectScenes,
+    splitAtScenes,
+    reviewScenes,
+    splitReviewed,
+    cancelReview: () => setReview(null),
+    clearError: () => setError(null),
+  };
+}
@@ -0,0 +1,182 @@
+import { useState, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { v4 as uuidv4 } from 'uuid';
+import type { MediaFile } from '../types';
+import { useProjectStore } from '../stores/projectStore';
+import { canSplitScenes } from './useScenes';
+
+export interface SilentRange {
+  start: number; // seconds into the source
+  end: number;
+}
+
+interface TrimSuggestion {
+  trim_start: number;
+  duration: number;
+}
+
+export interface SilenceDetection {
+  has_audio: boolean;
+  duration: number;
+  silences: SilentRange[];
+  suggested_trim: TrimSuggestion | null;
+}
+
+interface SpeechClip {
+  trim_start: number;
+  duration: number;
+}
+
+export interface SilenceOptions {
+  noiseDb?: number; // quieter than this is silence, -35 dB by default
+  minDuration?: number; // seconds, 0.5 by default
+}
+
+/** Silences longer than this are cut by default */
+export const DEFAULT_REMOVE_LONGER_THAN = 1.0;
+
+// In and out points closer than this are the same cut
+const TRIM_EPSILON = 0.01;
+
+/**
+ * Plain videos with a soundtrack to listen for silences in
+ */
+export const canRemoveSilences = (file: MediaFile): boolean => {
+  return canSplitScenes(file) && file.hasAudio === true;
+};
+
+export function useSilences() {
+  const mediaFiles = useProjectStore((state) => state.mediaFiles);
+  const updateMediaFile = useProjectStore((state) => state.updateMediaFile);
+  const replaceMediaFile = useProjectStore((state) => state.replaceMediaFile);
+  const [isProcessing, setIsProcessing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [processingId, setProcessingId] = useState<string | null>(null);
+
+  /**
+   * Silent ranges inside the part of the source a file plays, plus
+   * suggested in and out points
+   */
+  const detectSilences = useCallback(async (file: MediaFile, options: SilenceOptions = {}) => {
+    const detection = await invoke<SilenceDetection>('detect_silences', {
+      path: file.path,
+      noiseDb: options.noiseDb ?? null,
+      minDuration: options.minDuration ?? null,
+    });
+    const start = file.trimStart ?? 0;
+    const end = start + file.duration;
+    return {
+      ...detection,
+      silences: detection.silences.filter((s) => s.end > start && s.start < end),
+    };
+  }, []);
+
+  /**
+   * Apply the suggested trim, cutting silence at either end of a file
+   */
+  const trimSilentEnds = useCallback(async (file: MediaFile, options: SilenceOptions = {}) => {
+    const { suggested_trim } = await detectSilences(file, options);
+    if (!suggested_trim) return false;
+
+    const start = Math.max(suggested_trim.trim_start, file.trimStart ?? 0);
+    const end = Math.min(
+      suggested_trim.trim_start + suggested_trim.duration,
+      (file.trimStart ?? 0) + file.duration
+    );
+    if (end <= start) return false;
+    if (Math.abs(start - (file.trimStart ?? 0)) < TRIM_EPSILON && Math.abs(end - start - file.duration) < TRIM_EPSILON) {
+      return false;
+    }
+    updateMediaFile(file.id, { trimStart: start, duration: end - start });
+    return true;
+  }, [detectSilences, updateMediaFile]);
+
+  /**
+   * Replace a file with trimmed copies of the parts between silences
+   * longer than `longerThan` seconds. Nothing is re-encoded.
+   */
+  const removeSilences = useCallback(async (
+    file: MediaFile,
+    longerThan = DEFAULT_REMOVE_LONGER_THAN,
+    noiseDb?: number
+  ) => {
+    const clips = await invoke<SpeechClip[]>('remove_silences', {
+      path: file.path,
+      trimStart: file.trimStart ?? null,
+      duration: file.duration,
+      longerThan,
+      noiseDb: noiseDb ?? null,
+    });
+    if (clips.length === 0) return [file];
+    if (clips.length === 1) {
+      const [clip] = clips;
+      // Nothing cut - leave the file untouched so it isn't re-encoded
+      const start = file.trimStart ?? 0;
+      if (Math.abs(clip.trim_start - start) < TRIM_EPSILON && Math.abs(clip.duration - file.duration) < TRIM_EPSILON) {
+        return [file];
+      }
+      updateMediaFile(file.id, { trimStart: clip.trim_start, duration: clip.duration });
+      return [{ ...file, trimStart: clip.trim_start, duration: clip.duration }];
+    }
+
+    const start = file.trimStart ?? 0;
+    const pieces: MediaFile[] = clips.map((clip, i) => ({
+      ...file,
+      id: uuidv4(),
+      name: `${file.name} (${i + 1})`,
+      trimStart: clip.trim_start,
+      duration: clip.duration,
+      // Keeps the pieces in order when the timeline is re-sorted
+      timestamp: file.timestamp + (clip.trim_start - start) * 1000,
+    }));
+    replaceMediaFile(file.id, pieces);
+    return pieces;
+  }, [updateMediaFile, replaceMediaFile]);
+
+  /**
+   * Remove long silences from every plain video on the timeline
+   */
+  const removeAllSilences = useCallback(async (longerThan = DEFAULT_REMOVE_LONGER_THAN) => {
+    const targets = mediaFiles.filter(canRemoveSilences);
+    if (targets.length === 0) return;
+
+    setIsProcessing(true);
+    setError(null);
+    try {
+      for (const file of targets) {
+        await removeSilences(file, longerThan);
+      }
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsProcessing(false);
+    }
+  }, [mediaFiles, removeSilences]);
+
+  /**
+   * Run one of the silence tools on a single file, for the clip settings
+   */
+  const processFile = useCallback(async (file: MediaFile, action: (file: MediaFile) => Promise<unknown>) => {
+    setProcessingId(file.id);
+    setError(null);
+    try {
+      await action(file);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setProcessingId(null);
+    }
+  }, []);
+
+  return {
+    isProcessing,
+    processingId,
+    error,
+    detectSilences,
+    trimSilentEnds,
+    removeSilences,
+    removeAllSilences,
+    processFile,
+    clearError: () => setError(null),
+  };
+}
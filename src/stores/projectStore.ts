@@ -1,7 +1,8 @@
 import { create } from 'zustand';
 import { useShallow } from 'zustand/react/shallow';
-import type { ProjectStore, MediaFile, FormatPreset, ExportProgress, CoverConfig } from '../types';
+import type { ProjectStore, MediaFile, FormatPreset, ExportProgress, CoverConfig, FrameRateConversion } from '../types';
 import { DEFAULT_PRESET, getPresetByAspectRatio } from '../utils/presets';
+import { getTimelineDuration } from '../utils/mediaUtils';
 
 const initialCover: CoverConfig = {
   enabled: true, // kept for compatibility, but we derive from text
@@ -14,10 +15,13 @@ const initialState = {
   mediaFiles: [] as MediaFile[],
   cover: initialCover,
   selectedPreset: DEFAULT_PRESET as FormatPreset | null,
+  frameRateConversion: 'drop' as FrameRateConversion,
+  deinterlaceDoubleRate: false,
   isExporting: false,
   exportProgress: null as ExportProgress | null,
   previewTime: 0,
   isPlaying: false,
+  selectedMediaId: null as string | null,
 };
 
 export const useProjectStore = create<ProjectStore>((set) => ({
@@ -72,6 +76,13 @@ export const useProjectStore = create<ProjectStore>((set) => ({
     }));
   },
 
+  // Swap one item for several in its place, e.g. the scenes it was split into
+  replaceMediaFile: (id: string, files: MediaFile[]) => {
+    set((state) => ({
+      mediaFiles: state.mediaFiles.flatMap((f) => (f.id === id ? files : [f])),
+    }));
+  },
+
   // Cover actions
   setCoverEnabled: (enabled: boolean) => {
     set((state) => ({
@@ -96,6 +107,14 @@ export const useProjectStore = create<ProjectStore>((set) => ({
     set({ selectedPreset: preset });
   },
 
+  setFrameRateConversion: (mode: FrameRateConversion) => {
+    set({ frameRateConversion: mode });
+  },
+
+  setDeinterlaceDoubleRate: (doubleRate: boolean) => {
+    set({ deinterlaceDoubleRate: doubleRate });
+  },
+
   // Export actions
   setIsExporting: (isExporting: boolean) => {
     set({ isExporting });
@@ -114,6 +133,10 @@ export const useProjectStore = create<ProjectStore>((set) => ({
     set({ isPlaying });
   },
 
+  setSelectedMediaId: (id: string | null) => {
+    set({ selectedMediaId: id });
+  },
+
   // Project actions
   resetProject: () => {
     set(initialState);
@@ -125,11 +148,30 @@ export const useTotalDuration = () => {
   return useProjectStore((state) => {
     // Cover is active when text is not empty
     const coverDuration = state.cover.text.trim() ? state.cover.duration : 0;
-    const mediaDuration = state.mediaFiles.reduce((sum, file) => sum + file.duration, 0);
+    const mediaDuration = state.mediaFiles.reduce((sum, file) => sum + getTimelineDuration(file), 0);
     return coverDuration + mediaDuration;
   });
 };
 
+/**
+ * Seconds the playhead is into a clip's timeline slot, or null when it's
+ * on another clip
+ */
+export const usePlayheadOffset = (id: string) => {
+  return useProjectStore((state) => {
+    let start = state.cover.text.trim() ? state.cover.duration : 0;
+    for (const file of state.mediaFiles) {
+      const duration = getTimelineDuration(file);
+      if (file.id === id) {
+        const offset = state.previewTime - start;
+        return offset >= 0 && offset < duration ? offset : null;
+      }
+      start += duration;
+    }
+    return null;
+  });
+};
+
 export const useMediaCount = () => {
   return useProjectStore(
     useShallow((state) => ({
@@ -7,12 +7,75 @@ export interface MediaFile {
   path: string;
   type: MediaType;
   duration: number; // in seconds (4s for images, actual duration for videos)
-  timestamp: number; // file creation timestamp
-  thumbnail?: string; // base64 thumbnail
-  width?: number;
+  timestamp: number; // capture timestamp (QuickTime/EXIF/filename/filesystem)
+  thumbnail?: string; // qcthumb:// URL served from the backend cache
+  posterTime?: number; // source time of a user-chosen thumbnail frame (videos)
+  width?: number; // as displayed, after EXIF orientation
   height?: number;
   framerate?: number; // frames per second
   bitrate?: number; // bits per second
+  isVfr?: boolean; // variable frame rate - re-encoded to constant on export
+  isInterlaced?: boolean; // deinterlaced on export
+  hasAudio?: boolean;
+  isAnimated?: boolean; // animated GIF/WebP/PNG, played as a looping clip
+  loopDuration?: number; // one play-through of an animation, in seconds
+  loops?: number; // export this many loops instead of `duration`
+  imageSequence?: ImageSequenceInput; // numbered stills; `path` is the pattern
+  livePhoto?: LivePhotoMotion; // motion clip paired with this still
+  speed?: number; // playback speed, 0.25-8x (videos only)
+  muteAudio?: boolean;
+  playback?: PlaybackDirection;
+  transform?: ClipTransform; // framing; centre-fit when absent
+  contentRect?: CropRect; // picture inside burned-in black bars, cropped to on export
+  keepBlackBars?: boolean; // export the full frame anyway
+  trimStart?: number; // in point in the source; `duration` is the length kept from here
+}
+
+// Rectangle in source pixels, as displayed (after orientation)
+export interface CropRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+// Crop window `time` seconds into the source
+export interface CropKeyframe {
+  time: number;
+  rect: CropRect;
+}
+
+// Per-clip framing: crop, rotate, flip, then fit scaled by `zoom` and
+// shifted by `positionX`/`positionY` (-1 to 1, 0 = centred)
+export interface ClipTransform {
+  crop?: CropRect;
+  cropKeyframes?: CropKeyframe[]; // moving crop window, sized by the first keyframe
+  rotation?: 0 | 90 | 180 | 270; // clockwise
+  flipHorizontal?: boolean;
+  flipVertical?: boolean;
+  zoom?: number; // 1 = fit inside the frame
+  positionX?: number;
+  positionY?: number;
+}
+
+// How a video clip plays: as shot, backwards, or forwards then backwards
+export type PlaybackDirection = 'forward' | 'reverse' | 'boomerang';
+
+// How a Live Photo plays: the still, the motion clip, or the motion clip
+// followed by the still for the rest of the item's duration
+export type LivePhotoMode = 'still' | 'motion' | 'still_with_motion_intro';
+
+export interface LivePhotoMotion {
+  motionPath: string;
+  motionDuration: number;
+  mode: LivePhotoMode;
+}
+
+// Numbered stills played as one timelapse clip
+export interface ImageSequenceInput {
+  startNumber: number;
+  frameCount: number;
+  framerate: number;
 }
 
 // Cover text configuration
@@ -39,6 +102,10 @@ export interface FormatPreset {
   description: string;
 }
 
+// How clips at another frame rate are converted to the export rate:
+// drop/duplicate frames, blend neighbouring frames, or motion-interpolate
+export type FrameRateConversion = 'drop' | 'blend' | 'interpolate';
+
 // Export configuration
 export interface ExportConfig {
   preset: FormatPreset;
@@ -51,6 +118,7 @@ export interface ExportProgress {
   progress: number; // 0-100
   currentFile?: string;
   error?: string;
+  warning?: string; // e.g. audio and video ended out of sync
 }
 
 // Project state
@@ -63,6 +131,8 @@ export interface ProjectState {
 
   // Selected preset
   selectedPreset: FormatPreset | null;
+  frameRateConversion: FrameRateConversion;
+  deinterlaceDoubleRate: boolean; // one frame per field for interlaced clips (50i -> 50p)
 
   // Export state
   isExporting: boolean;
@@ -71,6 +141,7 @@ export interface ProjectState {
   // UI state
   previewTime: number;
   isPlaying: boolean;
+  selectedMediaId: string | null; // clip shown in the clip settings panel
 }
 
 // Store actions
@@ -81,6 +152,7 @@ export interface ProjectActions {
   reorderMediaFiles: (fromIndex: number, toIndex: number) => void;
   clearMediaFiles: () => void;
   updateMediaFile: (id: string, updates: Partial<MediaFile>) => void;
+  replaceMediaFile: (id: string, files: MediaFile[]) => void;
 
   // Cover actions
   setCoverEnabled: (enabled: boolean) => void;
@@ -89,6 +161,8 @@ export interface ProjectActions {
 
   // Preset actions
   setSelectedPreset: (preset: FormatPreset) => void;
+  setFrameRateConversion: (mode: FrameRateConversion) => void;
+  setDeinterlaceDoubleRate: (doubleRate: boolean) => void;
 
   // Export actions
   setIsExporting: (isExporting: boolean) => void;
@@ -97,6 +171,7 @@ export interface ProjectActions {
   // Preview actions
   setPreviewTime: (time: number) => void;
   setIsPlaying: (isPlaying: boolean) => void;
+  setSelectedMediaId: (id: string | null) => void;
 
   // Project actions
   resetProject: () => void;
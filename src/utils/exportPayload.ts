@@ -0,0 +1,131 @@
+import type { CoverConfig, CropRect, FormatPreset, FrameRateConversion, MediaFile } from '../types';
+import { getExportFramerate } from './mediaUtils';
+
+// Shapes the export and preview render commands take, in the backend's
+// snake_case
+
+export interface ExportConfigPayload {
+  preset_id: string;
+  width: number;
+  height: number;
+  codec: string;
+  framerate: number | null;
+  bitrate: number | null;
+  deinterlace_double_rate: boolean;
+  frame_rate_conversion: string;
+}
+
+export interface MediaItemPayload {
+  path: string;
+  media_type: string;
+  duration: number;
+  width: number | null;
+  height: number | null;
+  framerate: number | null;
+  is_vfr: boolean | null;
+  is_animated: boolean;
+  loop_duration: number | null;
+  loops: number | null;
+  image_sequence: { start_number: number; frame_count: number; framerate: number } | null;
+  live_photo: { motion_path: string; motion_duration: number; mode: string } | null;
+  speed: number | null;
+  mute_audio: boolean;
+  playback: string | null;
+  transform: {
+    crop: CropRect | null;
+    crop_keyframes: { time: number; rect: CropRect }[];
+    rotation: number;
+    flip_horizontal: boolean;
+    flip_vertical: boolean;
+    zoom: number;
+    position_x: number;
+    position_y: number;
+  } | null;
+  content_rect: CropRect | null;
+  keep_black_bars: boolean;
+  trim_start: number | null;
+}
+
+export interface CoverPayload {
+  enabled: boolean;
+  text: string;
+  duration: number;
+  color_scheme: string;
+}
+
+/**
+ * Export settings for a preset; frame rate and bitrate follow the first video
+ */
+export const toExportConfig = (
+  preset: FormatPreset,
+  mediaFiles: MediaFile[],
+  frameRateConversion: FrameRateConversion,
+  deinterlaceDoubleRate: boolean
+): ExportConfigPayload => {
+  const firstVideo = mediaFiles.find((f) => f.type === 'video');
+  return {
+    preset_id: preset.id,
+    width: preset.width,
+    height: preset.height,
+    codec: preset.codec,
+    framerate: firstVideo?.framerate ? getExportFramerate(mediaFiles, deinterlaceDoubleRate) : null,
+    bitrate: firstVideo?.bitrate ?? null,
+    deinterlace_double_rate: deinterlaceDoubleRate,
+    frame_rate_conversion: frameRateConversion,
+  };
+};
+
+export const toMediaItem = (file: MediaFile): MediaItemPayload => ({
+  path: file.path,
+  media_type: file.type,
+  duration: file.duration,
+  width: file.width ?? null,
+  height: file.height ?? null,
+  framerate: file.framerate ?? null,
+  is_vfr: file.isVfr ?? null,
+  is_animated: file.isAnimated ?? false,
+  loop_duration: file.loopDuration ?? null,
+  loops: file.loops ?? null,
+  image_sequence: file.imageSequence
+    ? {
+        start_number: file.imageSequence.startNumber,
+        frame_count: file.imageSequence.frameCount,
+        framerate: file.imageSequence.framerate,
+      }
+    : null,
+  live_photo: file.livePhoto
+    ? {
+        motion_path: file.livePhoto.motionPath,
+        motion_duration: file.livePhoto.motionDuration,
+        mode: file.livePhoto.mode,
+      }
+    : null,
+  speed: file.speed ?? null,
+  mute_audio: file.muteAudio ?? false,
+  playback: file.playback ?? null,
+  transform: file.transform
+    ? {
+        crop: file.transform.crop ?? null,
+        crop_keyframes: file.transform.cropKeyframes ?? [],
+        rotation: file.transform.rotation ?? 0,
+        flip_horizontal: file.transform.flipHorizontal ?? false,
+        flip_vertical: file.transform.flipVertical ?? false,
+        zoom: file.transform.zoom ?? 1,
+        position_x: file.transform.positionX ?? 0,
+        position_y: file.transform.positionY ?? 0,
+      }
+    : null,
+  content_rect: file.contentRect ?? null,
+  keep_black_bars: file.keepBlackBars ?? false,
+  trim_start: file.trimStart ?? null,
+});
+
+/**
+ * The cover is only rendered when it has text
+ */
+export const toCoverConfig = (cover: CoverConfig): CoverPayload => ({
+  enabled: cover.text.trim().length > 0,
+  text: cover.text,
+  duration: cover.duration,
+  color_scheme: cover.colorScheme,
+});
@@ -1,8 +1,16 @@
-import type { MediaFile, MediaType } from '../types';
+import type { FrameRateConversion, LivePhotoMode, MediaFile, MediaType } from '../types';
 
 // Supported file extensions
-export const VIDEO_EXTENSIONS = ['.mp4', '.mov', '.avi', '.mkv', '.webm'];
-export const IMAGE_EXTENSIONS = ['.jpg', '.jpeg', '.png', '.webp', '.gif'];
+export const VIDEO_EXTENSIONS = [
+  '.mp4', '.mov', '.avi', '.mkv', '.webm',
+  '.m4v', '.3gp', '.mts', '.m2ts', '.mpg', '.mpeg', '.wmv', '.flv', '.dv',
+];
+export const RAW_EXTENSIONS = ['.dng', '.cr2', '.cr3', '.nef', '.arw', '.raf', '.orf', '.rw2', '.pef', '.srw'];
+export const IMAGE_EXTENSIONS = [
+  '.jpg', '.jpeg', '.png', '.webp', '.gif',
+  '.heic', '.heif', '.avif', '.tif', '.tiff', '.bmp',
+  ...RAW_EXTENSIONS,
+];
 export const ALL_EXTENSIONS = [...VIDEO_EXTENSIONS, ...IMAGE_EXTENSIONS];
 
 // Default duration for images (in seconds)
@@ -21,6 +29,108 @@ export const getMediaType = (filename: string): MediaType | null => {
   return null;
 };
 
+/**
+ * Changes to apply when switching a Live Photo's playback mode, keeping its
+ * duration in step: the still's default, the motion clip's length, or both
+ */
+export const livePhotoModeUpdate = (file: MediaFile, mode: LivePhotoMode): Partial<MediaFile> => {
+  if (!file.livePhoto) return {};
+  const motion = file.livePhoto.motionDuration;
+  const duration = mode === 'motion'
+    ? motion
+    : mode === 'still_with_motion_intro'
+      ? motion + DEFAULT_IMAGE_DURATION
+      : DEFAULT_IMAGE_DURATION;
+  return { duration, livePhoto: { ...file.livePhoto, mode } };
+};
+
+// Per-clip speed limits, matching the export backend
+export const MIN_SPEED = 0.25;
+export const MAX_SPEED = 8;
+
+/**
+ * Playback speed of a file; only videos can be sped up or slowed down
+ */
+export const getSpeed = (file: MediaFile): number => {
+  if (file.type !== 'video' || file.isAnimated) return 1;
+  return Math.min(Math.max(file.speed ?? 1, MIN_SPEED), MAX_SPEED);
+};
+
+/**
+ * Seconds a file fills on the timeline: its duration after speed,
+ * boomerang and loops. Images play their duration as-is, and animations
+ * either that or a chosen number of loops, as the export does.
+ */
+export const getTimelineDuration = (file: MediaFile): number => {
+  if (file.isAnimated && file.loops && file.loopDuration) {
+    return Math.max(file.loops, 1) * file.loopDuration;
+  }
+  if (file.type !== 'video' || file.isAnimated) return file.duration;
+  const speed = getSpeed(file);
+  const passes = file.playback === 'boomerang' ? 2 : 1;
+  const loops = Math.max(file.loops ?? 1, 1);
+  return (file.duration / speed) * passes * loops;
+};
+
+/**
+ * Position in the source file shown `offset` seconds into a video's
+ * timeline slot, after trim, speed, loops and direction
+ */
+export const getSourceTime = (file: MediaFile, offset: number): number => {
+  if (file.type !== 'video' || file.isAnimated || file.duration <= 0) return offset;
+  const start = file.imageSequence ? 0 : (file.trimStart ?? 0);
+  const d = file.duration;
+  const t = offset * getSpeed(file);
+  if (file.playback === 'boomerang') {
+    const pass = t % (2 * d);
+    return start + (pass > d ? 2 * d - pass : pass);
+  }
+  if (file.playback === 'reverse') return start + d - (t % d);
+  return start + (t % d);
+};
+
+// Rough seconds of work per second of output on a typical machine.
+// Motion interpolation is far slower than the encode itself.
+const ENCODE_COST = 0.3;
+const FPS_CONVERSION_COST: Record<FrameRateConversion, number> = {
+  drop: 0,
+  blend: 0.2,
+  interpolate: 8,
+};
+
+/**
+ * Export frame rate: the first video's, as sent with the export config.
+ * Double-rate deinterlacing of an interlaced first video keeps one frame
+ * per field, so the export runs at the field rate.
+ */
+export const getExportFramerate = (files: MediaFile[], deinterlaceDoubleRate = false): number => {
+  const firstVideo = files.find((f) => f.type === 'video');
+  const framerate = firstVideo?.framerate ?? 30;
+  return deinterlaceDoubleRate && firstVideo?.isInterlaced ? framerate * 2 : framerate;
+};
+
+/**
+ * Rough export time in seconds: encoding the whole timeline, plus
+ * converting clips that aren't already at the export frame rate
+ */
+export const estimateExportTime = (
+  files: MediaFile[],
+  coverDuration: number,
+  mode: FrameRateConversion,
+  deinterlaceDoubleRate = false
+): number => {
+  const framerate = getExportFramerate(files, deinterlaceDoubleRate);
+  return files.reduce((sum, file) => {
+    const duration = getTimelineDuration(file);
+    // Same rule as the backend: only constant-rate footage is converted
+    const converts = file.type === 'video' && !file.isAnimated && !file.isVfr
+      && file.framerate !== undefined
+      && Math.abs(file.framerate * getSpeed(file) - framerate) > 0.5;
+    const conversion = converts ? FPS_CONVERSION_COST[mode] : 0;
+    return sum + duration * (ENCODE_COST + conversion);
+  }, coverDuration * ENCODE_COST);
+};
+
 /**
  * Check if a file is a supported media type
  */